@@ -2,7 +2,7 @@
 pub mod mesh;
 pub mod models;
 
-use odc_core::mdl::{RenderModel, Size2d};
+use odc_core::mdl::{PassId, PipelineId, RenderModel, Size2d, TextureId};
 use odc_core::{DrawData, OdcCore, RenderStep, WindowInfo};
 use std::collections::HashMap;
 use winit::dpi::PhysicalSize;
@@ -12,7 +12,7 @@ use winit::window::WindowBuilder;
 
 pub trait Example {
     fn render_model() -> RenderModel;
-    fn windows() -> Vec<(usize, String, Size2d)>;
+    fn windows() -> Vec<(TextureId, String, Size2d)>;
     fn init(&mut self, renderer: &mut OdcCore);
     fn update(&mut self, renderer: &mut OdcCore);
     fn draw_data(&self) -> Vec<DrawDataStorage>;
@@ -28,7 +28,7 @@ pub fn run_example<E: Example + 'static>(mut ex: E) -> ! {
     let window_handles: HashMap<_, _> = windows
         .iter()
         .map(|(source, name, size)| {
-            let window_size = PhysicalSize::new(size.x as u32, size.y as u32);
+            let window_size = PhysicalSize::new(size.x, size.y);
             let window = WindowBuilder::default()
                 .with_title(name)
                 .with_inner_size(window_size)
@@ -69,7 +69,7 @@ pub fn run_example<E: Example + 'static>(mut ex: E) -> ! {
             }
             Event::MainEventsCleared => {
                 let data = ex.draw_data();
-                let steps = data.iter().map(Into::into);
+                let steps = data.iter().map(RenderStep::from);
                 renderer.draw(steps);
             }
             Event::WindowEvent {
@@ -87,8 +87,8 @@ pub fn run_example<E: Example + 'static>(mut ex: E) -> ! {
 }
 
 pub struct DrawDataStorage {
-    pub pass: usize,
-    pub pipeline: usize,
+    pub pass: PassId,
+    pub pipeline: PipelineId,
     pub data: Vec<DrawData>,
 }
 
@@ -98,6 +98,10 @@ impl<'a> From<&'a DrawDataStorage> for RenderStep<'a> {
             pass: s.pass,
             pipeline: s.pipeline,
             data: &s.data,
+            stencil_reference: 0,
+            viewport: None,
+            scissor: None,
+            blend_constant: None,
         }
     }
 }
@@ -1,11 +1,13 @@
-use super::{MAT4_SIZE, VEC4_SIZE};
+use super::{
+    buffers, INDEX_BUFFER, INSTANCE_BUFFER, MAT4_SIZE, UNIFORM_BUFFER, VEC4_SIZE, VERTEX_BUFFER,
+};
 use odc_core::mdl::*;
 
 const UNIFORM_SIZE: u64 = MAT4_SIZE * 2;
 const WINDOW_SIZE: Size2d = Size2d { x: 800, y: 600 };
 
 pub fn color_mesh_model() -> RenderModel {
-    let buffers = buffers();
+    let buffers = buffers(UNIFORM_SIZE);
     let textures = textures();
     let samplers = vec![];
 
@@ -17,6 +19,7 @@ pub fn color_mesh_model() -> RenderModel {
     RenderModel {
         passes,
         pipelines,
+        compute_pipelines: vec![],
         bind_groups,
         textures,
         buffers,
@@ -24,35 +27,32 @@ pub fn color_mesh_model() -> RenderModel {
     }
 }
 
-fn buffers() -> Buffers {
-    Buffers {
-        index: 2u64.pow(10),
-        vertex: 2u64.pow(10),
-        instance: 2u64.pow(16),
-        uniform: UNIFORM_SIZE,
-    }
-}
-
 fn textures() -> Vec<Texture> {
     let color_texture = Texture {
+        name: Some("color".into()),
         typ: TextureType::Color {
             texel: TexelType::Unorm,
             texel_count: TexelCount::Four,
         },
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     let depth_texture = Texture {
-        typ: TextureType::Depth,
+        name: Some("depth".into()),
+        typ: TextureType::Depth(DepthFormat::Depth32Float),
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     vec![color_texture, depth_texture]
@@ -63,12 +63,15 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 0,
         shader_stages: ShaderStages::Vertex,
         info: UniformInfo {
+            buffer: UNIFORM_BUFFER,
             size: UNIFORM_SIZE,
             offset: 0,
+            dynamic_offset: false,
         },
     };
     let bind_group = BindGroup {
-        uniform: Some(uniform),
+        name: Some("camera".into()),
+        uniforms: vec![uniform],
         ..Default::default()
     };
 
@@ -88,7 +91,9 @@ fn pipelines() -> Vec<RenderPipeline> {
             location: 1,
         },
     ];
-    let vertex_buffer = InputInfo {
+    let vertex_buffer = VertexStream {
+        buffer: VERTEX_BUFFER,
+        step_mode: StepMode::Vertex,
         attributes,
         stride: VEC4_SIZE * 2,
     };
@@ -115,7 +120,9 @@ fn pipelines() -> Vec<RenderPipeline> {
             location: 5,
         },
     ];
-    let instance_buffer = InputInfo {
+    let instance_buffer = VertexStream {
+        buffer: INSTANCE_BUFFER,
+        step_mode: StepMode::Instance,
         attributes,
         stride: MAT4_SIZE,
     };
@@ -127,15 +134,22 @@ fn pipelines() -> Vec<RenderPipeline> {
     };
 
     let pipeline = RenderPipeline {
-        input: Some(PipelineInpit {
-            vertex: vertex_buffer,
-            instance: instance_buffer,
-        }),
-        bind_groups: vec![0],
-        blend: vec![None],
+        name: Some("color_mesh".into()),
+        input: PipelineInpit {
+            index: Some(INDEX_BUFFER),
+            index_format: IndexFormat::Uint32,
+            streams: vec![vertex_buffer, instance_buffer],
+        },
+        bind_groups: vec![BindGroupId(0)],
+        push_constants: vec![],
+        targets: vec![ColorTarget::default()],
         shader,
-        depth: Some(DepthOps {}),
-        multisampled: false,
+        depth: Some(DepthOps::default()),
+        multisample: Multisample::default(),
+        primitive: Primitive {
+            cull_mode: CullMode::Front,
+            ..Default::default()
+        },
     };
 
     vec![pipeline]
@@ -143,14 +157,19 @@ fn pipelines() -> Vec<RenderPipeline> {
 
 fn passes() -> Vec<Pass> {
     let pass = Pass {
-        pipelines: vec![0],
+        name: Some("main".into()),
+        pipelines: vec![PipelineId(0)],
         color_attachments: vec![Attachment {
-            texture: 0,
+            texture: TextureId(0),
             resolve: None,
             clear: Some([0.0, 0.0, 0.0, 1.0]),
             store: true,
+            range: ViewRange::default(),
         }],
-        depth_attachment: Some(DepthAttachment { texture: 1 }),
+        depth_attachment: Some(DepthAttachment {
+            texture: TextureId(1),
+            ..Default::default()
+        }),
     };
 
     vec![pass]
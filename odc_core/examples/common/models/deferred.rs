@@ -1,11 +1,13 @@
-use super::{MAT4_SIZE, VEC4_SIZE};
+use super::{
+    buffers, INDEX_BUFFER, INSTANCE_BUFFER, MAT4_SIZE, UNIFORM_BUFFER, VEC4_SIZE, VERTEX_BUFFER,
+};
 use odc_core::mdl::*;
 
 const UNIFORM_SIZE: u64 = MAT4_SIZE * 2;
 const WINDOW_SIZE: Size2d = Size2d { x: 800, y: 600 };
 
 pub fn deferred_model() -> RenderModel {
-    let buffers = buffers();
+    let buffers = buffers(UNIFORM_SIZE);
     let textures = textures();
     let samplers = samplers();
 
@@ -17,6 +19,7 @@ pub fn deferred_model() -> RenderModel {
     RenderModel {
         passes,
         pipelines,
+        compute_pipelines: vec![],
         bind_groups,
         textures,
         buffers,
@@ -24,71 +27,77 @@ pub fn deferred_model() -> RenderModel {
     }
 }
 
-fn buffers() -> Buffers {
-    Buffers {
-        index: 2u64.pow(10),
-        vertex: 2u64.pow(10),
-        instance: 2u64.pow(16),
-        uniform: UNIFORM_SIZE,
-    }
-}
-
 fn textures() -> Vec<Texture> {
     let position = Texture {
+        name: Some("gbuffer_position".into()),
         typ: TextureType::Color {
             texel: TexelType::Float(BytesPerFloatTexel::Four),
             texel_count: TexelCount::Four,
         },
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     let albedo = Texture {
+        name: Some("gbuffer_albedo".into()),
         typ: TextureType::Color {
             texel: TexelType::Unorm,
             texel_count: TexelCount::Four,
         },
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     let light = Texture {
+        name: Some("light".into()),
         typ: TextureType::Color {
             texel: TexelType::Unorm,
             texel_count: TexelCount::Four,
         },
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     let final_tex = Texture {
+        name: Some("final".into()),
         typ: TextureType::Color {
             texel: TexelType::Unorm,
             texel_count: TexelCount::Four,
         },
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     let depth = Texture {
-        typ: TextureType::Depth,
+        name: Some("depth".into()),
+        typ: TextureType::Depth(DepthFormat::Depth32Float),
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     vec![position, albedo, light, depth, final_tex]
@@ -97,6 +106,7 @@ fn textures() -> Vec<Texture> {
 fn samplers() -> Vec<Sampler> {
     let typ = SamplerType::NonFilter;
     let sampler = Sampler {
+        name: Some("nearest".into()),
         typ,
         ..Default::default()
     };
@@ -108,13 +118,16 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 0,
         shader_stages: ShaderStages::Vertex,
         info: UniformInfo {
+            buffer: UNIFORM_BUFFER,
             size: UNIFORM_SIZE,
             offset: 0,
+            dynamic_offset: false,
         },
     };
 
     let deferred_group = BindGroup {
-        uniform: Some(uniform),
+        name: Some("deferred".into()),
+        uniforms: vec![uniform],
         ..Default::default()
     };
 
@@ -122,29 +135,35 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 0,
         shader_stages: ShaderStages::Fragment,
         info: TextureInfo {
-            texture: 0,
+            texture: TextureId(0),
             dimension: TextureViewDimension::D2,
+            range: ViewRange::default(),
         },
     };
 
     let position_sampler = Binding {
         index: 2,
         shader_stages: ShaderStages::Fragment,
-        info: SamplerInfo { sampler: 0 },
+        info: SamplerInfo {
+            sampler: SamplerId(0),
+        },
     };
 
     let light_group = BindGroup {
-        uniform: None,
+        name: Some("light".into()),
+        uniforms: vec![],
         textures: vec![position_texture],
         samplers: vec![position_sampler],
+        ..Default::default()
     };
 
     let albedo_texture = Binding {
         index: 0,
         shader_stages: ShaderStages::Fragment,
         info: TextureInfo {
-            texture: 1,
+            texture: TextureId(1),
             dimension: TextureViewDimension::D2,
+            range: ViewRange::default(),
         },
     };
 
@@ -152,21 +171,26 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 1,
         shader_stages: ShaderStages::Fragment,
         info: TextureInfo {
-            texture: 2,
+            texture: TextureId(2),
             dimension: TextureViewDimension::D2,
+            range: ViewRange::default(),
         },
     };
 
     let albedo_light_sampler = Binding {
         index: 2,
         shader_stages: ShaderStages::Fragment,
-        info: SamplerInfo { sampler: 0 },
+        info: SamplerInfo {
+            sampler: SamplerId(0),
+        },
     };
 
     let final_group = BindGroup {
-        uniform: None,
+        name: Some("final".into()),
+        uniforms: vec![],
         textures: vec![albedo_texture, light_texture],
         samplers: vec![albedo_light_sampler],
+        ..Default::default()
     };
 
     vec![deferred_group, light_group, final_group]
@@ -185,7 +209,9 @@ fn position_pipeline() -> RenderPipeline {
             location: 1,
         },
     ];
-    let vertex_buffer = InputInfo {
+    let vertex_buffer = VertexStream {
+        buffer: VERTEX_BUFFER,
+        step_mode: StepMode::Vertex,
         attributes,
         stride: VEC4_SIZE * 2,
     };
@@ -212,7 +238,9 @@ fn position_pipeline() -> RenderPipeline {
             location: 5,
         },
     ];
-    let instance_buffer = InputInfo {
+    let instance_buffer = VertexStream {
+        buffer: INSTANCE_BUFFER,
+        step_mode: StepMode::Instance,
         attributes,
         stride: MAT4_SIZE,
     };
@@ -224,37 +252,35 @@ fn position_pipeline() -> RenderPipeline {
     };
 
     RenderPipeline {
-        input: Some(PipelineInpit {
-            vertex: vertex_buffer,
-            instance: instance_buffer,
-        }),
-        bind_groups: vec![0],
+        name: Some("deferred".into()),
+        input: PipelineInpit {
+            index: Some(INDEX_BUFFER),
+            index_format: IndexFormat::Uint32,
+            streams: vec![vertex_buffer, instance_buffer],
+        },
+        bind_groups: vec![BindGroupId(0)],
+        push_constants: vec![],
         shader,
-        blend: vec![None, None],
-        depth: Some(DepthOps {}),
-        multisampled: false,
+        targets: vec![ColorTarget::default(), ColorTarget::default()],
+        depth: Some(DepthOps::default()),
+        multisample: Multisample::default(),
+        primitive: Primitive {
+            cull_mode: CullMode::Front,
+            ..Default::default()
+        },
     }
 }
 
 fn light_pipeline() -> RenderPipeline {
-    let attributes = vec![InputAttribute {
-        item: InputItem::Float32x4,
-        offset: 0,
-        location: 0,
-    }];
-
-    let vertex_buffer = InputInfo {
-        attributes, // TODO: Without it wgpu will skip this buffer and vertex buffer will be used instead instance buffer.
-        stride: 0,
-    };
-
     let attributes = vec![InputAttribute {
         item: InputItem::Float32x4,
         offset: 0,
         location: 1,
     }];
 
-    let instance_buffer = InputInfo {
+    let instance_buffer = VertexStream {
+        buffer: INSTANCE_BUFFER,
+        step_mode: StepMode::Instance,
         attributes,
         stride: VEC4_SIZE,
     };
@@ -275,15 +301,25 @@ fn light_pipeline() -> RenderPipeline {
     };
 
     RenderPipeline {
-        input: Some(PipelineInpit {
-            vertex: vertex_buffer,
-            instance: instance_buffer,
-        }),
-        bind_groups: vec![1],
+        name: Some("light".into()),
+        input: PipelineInpit {
+            index: Some(INDEX_BUFFER),
+            index_format: IndexFormat::Uint32,
+            streams: vec![instance_buffer],
+        },
+        bind_groups: vec![BindGroupId(1)],
+        push_constants: vec![],
         shader,
-        blend: vec![Some(blend)],
+        targets: vec![ColorTarget {
+            blend: Some(blend),
+            write_mask: ColorWrites::ALL,
+        }],
         depth: None,
-        multisampled: false,
+        multisample: Multisample::default(),
+        primitive: Primitive {
+            cull_mode: CullMode::Front,
+            ..Default::default()
+        },
     }
 }
 
@@ -295,12 +331,18 @@ fn final_pipeline() -> RenderPipeline {
     };
 
     RenderPipeline {
-        input: None,
-        bind_groups: vec![2],
+        name: Some("final".into()),
+        input: PipelineInpit::default(),
+        bind_groups: vec![BindGroupId(2)],
+        push_constants: vec![],
         shader,
-        blend: vec![None],
+        targets: vec![ColorTarget::default()],
         depth: None,
-        multisampled: false,
+        multisample: Multisample::default(),
+        primitive: Primitive {
+            cull_mode: CullMode::Front,
+            ..Default::default()
+        },
     }
 }
 
@@ -310,43 +352,53 @@ fn pipelines() -> Vec<RenderPipeline> {
 
 fn passes() -> Vec<Pass> {
     let position_attachment = Attachment {
-        texture: 0,
+        texture: TextureId(0),
         resolve: None,
         clear: Some([0.0, 0.0, 0.0, 0.0]),
         store: true,
+        range: ViewRange::default(),
     };
 
     let albedo_attachment = Attachment {
-        texture: 1,
+        texture: TextureId(1),
         resolve: None,
         clear: Some([0.0, 0.0, 0.0, 0.0]),
         store: true,
+        range: ViewRange::default(),
     };
 
     let deferred = Pass {
-        pipelines: vec![0],
+        name: Some("deferred".into()),
+        pipelines: vec![PipelineId(0)],
         color_attachments: vec![position_attachment, albedo_attachment],
-        depth_attachment: Some(DepthAttachment { texture: 3 }),
+        depth_attachment: Some(DepthAttachment {
+            texture: TextureId(3),
+            ..Default::default()
+        }),
     };
 
     let light = Pass {
-        pipelines: vec![1],
+        name: Some("light".into()),
+        pipelines: vec![PipelineId(1)],
         color_attachments: vec![Attachment {
-            texture: 2,
+            texture: TextureId(2),
             resolve: None,
             clear: Some([0.0, 0.0, 0.0, 0.0]),
             store: true,
+            range: ViewRange::default(),
         }],
         depth_attachment: None,
     };
 
     let final_pass = Pass {
-        pipelines: vec![2],
+        name: Some("final".into()),
+        pipelines: vec![PipelineId(2)],
         color_attachments: vec![Attachment {
-            texture: 4,
+            texture: TextureId(4),
             resolve: None,
             clear: Some([0.0, 0.0, 0.0, 1.0]),
             store: true,
+            range: ViewRange::default(),
         }],
         depth_attachment: None,
     };
@@ -1,11 +1,58 @@
+use odc_core::mdl::{Buffer, BufferId, BufferUsage};
 use std::mem;
 
 const VEC4_SIZE: u64 = mem::size_of::<[f32; 4]>() as _;
 const MAT4_SIZE: u64 = VEC4_SIZE * 4;
 
+pub const INDEX_BUFFER: BufferId = BufferId(0);
+pub const VERTEX_BUFFER: BufferId = BufferId(1);
+pub const INSTANCE_BUFFER: BufferId = BufferId(2);
+pub const UNIFORM_BUFFER: BufferId = BufferId(3);
+
 pub mod color_mesh;
 pub mod deferred;
 pub mod mip_map;
 pub mod msaa_color_mesh;
 pub mod skybox;
 pub mod sprites;
+
+/// Index, vertex, instance and uniform buffers shared by example models.
+fn buffers(uniform_size: u64) -> Vec<Buffer> {
+    let index = Buffer {
+        name: Some("index".into()),
+        size: 2u64.pow(10),
+        usage: BufferUsage {
+            index: true,
+            ..Default::default()
+        },
+    };
+
+    let vertex = Buffer {
+        name: Some("vertex".into()),
+        size: 2u64.pow(10),
+        usage: BufferUsage {
+            vertex: true,
+            ..Default::default()
+        },
+    };
+
+    let instance = Buffer {
+        name: Some("instance".into()),
+        size: 2u64.pow(16),
+        usage: BufferUsage {
+            vertex: true,
+            ..Default::default()
+        },
+    };
+
+    let uniform = Buffer {
+        name: Some("uniform".into()),
+        size: uniform_size,
+        usage: BufferUsage {
+            uniform: true,
+            ..Default::default()
+        },
+    };
+
+    vec![index, vertex, instance, uniform]
+}
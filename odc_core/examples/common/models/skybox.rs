@@ -1,11 +1,11 @@
-use super::{MAT4_SIZE, VEC4_SIZE};
+use super::{buffers, INDEX_BUFFER, MAT4_SIZE, UNIFORM_BUFFER, VEC4_SIZE, VERTEX_BUFFER};
 use odc_core::mdl::*;
 
 const UNIFORM_SIZE: u64 = MAT4_SIZE * 2;
 const WINDOW_SIZE: Size2d = Size2d { x: 800, y: 600 };
 
 pub fn skybox_model() -> RenderModel {
-    let buffers = buffers();
+    let buffers = buffers(UNIFORM_SIZE);
     let textures = textures();
     let samplers = samplers();
 
@@ -17,6 +17,7 @@ pub fn skybox_model() -> RenderModel {
     RenderModel {
         passes,
         pipelines,
+        compute_pipelines: vec![],
         bind_groups,
         textures,
         buffers,
@@ -24,26 +25,20 @@ pub fn skybox_model() -> RenderModel {
     }
 }
 
-fn buffers() -> Buffers {
-    Buffers {
-        index: 2u64.pow(10),
-        vertex: 2u64.pow(10),
-        instance: 2u64.pow(16),
-        uniform: UNIFORM_SIZE,
-    }
-}
-
 fn textures() -> Vec<Texture> {
     let color_texture = Texture {
+        name: Some("color".into()),
         typ: TextureType::Color {
             texel: TexelType::Unorm,
             texel_count: TexelCount::Four,
         },
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     let size = Extent3d {
@@ -52,12 +47,15 @@ fn textures() -> Vec<Texture> {
         depth_or_array_layers: 6,
     };
     let cubemap = Texture {
+        name: Some("cubemap".into()),
         typ: TextureType::Srgb,
+        dimension: TextureDimension::D2,
         size,
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: false,
         writable: true,
+        auto_mipmaps: false,
     };
 
     vec![color_texture, cubemap]
@@ -66,6 +64,7 @@ fn textures() -> Vec<Texture> {
 fn samplers() -> Vec<Sampler> {
     let typ = SamplerType::Filter(FilterMode::Linear);
     let sampler = Sampler {
+        name: Some("linear".into()),
         typ,
         ..Default::default()
     };
@@ -77,8 +76,10 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 0,
         shader_stages: ShaderStages::Vertex,
         info: UniformInfo {
+            buffer: UNIFORM_BUFFER,
             size: UNIFORM_SIZE,
             offset: 0,
+            dynamic_offset: false,
         },
     };
 
@@ -86,21 +87,26 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 1,
         shader_stages: ShaderStages::Fragment,
         info: TextureInfo {
-            texture: 1,
+            texture: TextureId(1),
             dimension: TextureViewDimension::Cube,
+            range: ViewRange::default(),
         },
     };
 
     let sampler = Binding {
         index: 2,
         shader_stages: ShaderStages::Fragment,
-        info: SamplerInfo { sampler: 0 },
+        info: SamplerInfo {
+            sampler: SamplerId(0),
+        },
     };
 
     let bind_group = BindGroup {
-        uniform: Some(uniform),
+        name: Some("main".into()),
+        uniforms: vec![uniform],
         textures: vec![skybox],
         samplers: vec![sampler],
+        ..Default::default()
     };
 
     vec![bind_group]
@@ -112,18 +118,13 @@ fn pipelines() -> Vec<RenderPipeline> {
         offset: 0,
         location: 0,
     }];
-    let vertex_buffer = InputInfo {
+    let vertex_buffer = VertexStream {
+        buffer: VERTEX_BUFFER,
+        step_mode: StepMode::Vertex,
         attributes,
         stride: VEC4_SIZE,
     };
 
-    let attributes = vec![];
-
-    let instance_buffer = InputInfo {
-        attributes,
-        stride: 0,
-    };
-
     let shader = Shader {
         path: "odc_core/examples/shaders/skybox.wgsl".into(),
         vs_main: "vs_main".into(),
@@ -131,15 +132,22 @@ fn pipelines() -> Vec<RenderPipeline> {
     };
 
     let pipeline = RenderPipeline {
-        input: Some(PipelineInpit {
-            vertex: vertex_buffer,
-            instance: instance_buffer,
-        }),
-        bind_groups: vec![0],
-        blend: vec![None],
+        name: Some("skybox".into()),
+        input: PipelineInpit {
+            index: Some(INDEX_BUFFER),
+            index_format: IndexFormat::Uint32,
+            streams: vec![vertex_buffer],
+        },
+        bind_groups: vec![BindGroupId(0)],
+        push_constants: vec![],
+        targets: vec![ColorTarget::default()],
         shader,
         depth: None,
-        multisampled: false,
+        multisample: Multisample::default(),
+        primitive: Primitive {
+            cull_mode: CullMode::Front,
+            ..Default::default()
+        },
     };
 
     vec![pipeline]
@@ -147,12 +155,14 @@ fn pipelines() -> Vec<RenderPipeline> {
 
 fn passes() -> Vec<Pass> {
     let pass = Pass {
-        pipelines: vec![0],
+        name: Some("main".into()),
+        pipelines: vec![PipelineId(0)],
         color_attachments: vec![Attachment {
-            texture: 0,
+            texture: TextureId(0),
             resolve: None,
             clear: Some([0.0, 0.0, 0.0, 0.0]),
             store: true,
+            range: ViewRange::default(),
         }],
         depth_attachment: None,
     };
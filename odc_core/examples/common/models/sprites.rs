@@ -1,11 +1,13 @@
-use super::{MAT4_SIZE, VEC4_SIZE};
+use super::{
+    buffers, INDEX_BUFFER, INSTANCE_BUFFER, MAT4_SIZE, UNIFORM_BUFFER, VEC4_SIZE, VERTEX_BUFFER,
+};
 use odc_core::mdl::*;
 
 const UNIFORM_SIZE: u64 = MAT4_SIZE * 2;
 const WINDOW_SIZE: Size2d = Size2d { x: 800, y: 600 };
 
 pub fn sprites_model() -> RenderModel {
-    let buffers = buffers();
+    let buffers = buffers(UNIFORM_SIZE);
     let textures = textures();
     let samplers = samplers();
 
@@ -17,6 +19,7 @@ pub fn sprites_model() -> RenderModel {
     RenderModel {
         passes,
         pipelines,
+        compute_pipelines: vec![],
         bind_groups,
         textures,
         buffers,
@@ -24,36 +27,33 @@ pub fn sprites_model() -> RenderModel {
     }
 }
 
-fn buffers() -> Buffers {
-    Buffers {
-        index: 2u64.pow(10),
-        vertex: 2u64.pow(10),
-        instance: 2u64.pow(16),
-        uniform: UNIFORM_SIZE,
-    }
-}
-
 fn textures() -> Vec<Texture> {
     let color_texture = Texture {
+        name: Some("color".into()),
         typ: TextureType::Color {
             texel: TexelType::Unorm,
             texel_count: TexelCount::Four,
         },
+        dimension: TextureDimension::D2,
         size: WINDOW_SIZE.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: false,
+        auto_mipmaps: false,
     };
 
     let atlas_size = Size2d::from((256, 128));
     let sprite_atlas = Texture {
+        name: Some("sprite_atlas".into()),
         typ: TextureType::Srgb,
+        dimension: TextureDimension::D2,
         size: atlas_size.into(),
         mip_levels: 1,
-        multisampled: false,
+        sample_count: SampleCount::One,
         window_source: true,
         writable: true,
+        auto_mipmaps: false,
     };
 
     vec![color_texture, sprite_atlas]
@@ -62,6 +62,7 @@ fn textures() -> Vec<Texture> {
 fn samplers() -> Vec<Sampler> {
     let typ = SamplerType::Filter(FilterMode::Linear);
     let sampler = Sampler {
+        name: Some("linear".into()),
         typ,
         ..Default::default()
     };
@@ -73,8 +74,10 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 0,
         shader_stages: ShaderStages::Vertex,
         info: UniformInfo {
+            buffer: UNIFORM_BUFFER,
             size: UNIFORM_SIZE,
             offset: 0,
+            dynamic_offset: false,
         },
     };
 
@@ -82,21 +85,26 @@ fn bind_groups() -> Vec<BindGroup> {
         index: 1,
         shader_stages: ShaderStages::Fragment,
         info: TextureInfo {
-            texture: 1,
+            texture: TextureId(1),
             dimension: TextureViewDimension::D2,
+            range: ViewRange::default(),
         },
     };
 
     let sampler = Binding {
         index: 2,
         shader_stages: ShaderStages::Fragment,
-        info: SamplerInfo { sampler: 0 },
+        info: SamplerInfo {
+            sampler: SamplerId(0),
+        },
     };
 
     let bind_group = BindGroup {
-        uniform: Some(uniform),
+        name: Some("main".into()),
+        uniforms: vec![uniform],
         textures: vec![sprite],
         samplers: vec![sampler],
+        ..Default::default()
     };
 
     vec![bind_group]
@@ -115,7 +123,9 @@ fn pipelines() -> Vec<RenderPipeline> {
             location: 1,
         },
     ];
-    let vertex_buffer = InputInfo {
+    let vertex_buffer = VertexStream {
+        buffer: VERTEX_BUFFER,
+        step_mode: StepMode::Vertex,
         attributes,
         stride: VEC4_SIZE * 2,
     };
@@ -148,7 +158,9 @@ fn pipelines() -> Vec<RenderPipeline> {
         },
     ];
 
-    let instance_buffer = InputInfo {
+    let instance_buffer = VertexStream {
+        buffer: INSTANCE_BUFFER,
+        step_mode: StepMode::Instance,
         attributes,
         stride: MAT4_SIZE + VEC4_SIZE,
     };
@@ -160,15 +172,22 @@ fn pipelines() -> Vec<RenderPipeline> {
     };
 
     let pipeline = RenderPipeline {
-        input: Some(PipelineInpit {
-            vertex: vertex_buffer,
-            instance: instance_buffer,
-        }),
-        bind_groups: vec![0],
-        blend: vec![Some(BlendState::ALPHA_BLENDING)],
+        name: Some("sprites".into()),
+        input: PipelineInpit {
+            index: Some(INDEX_BUFFER),
+            index_format: IndexFormat::Uint32,
+            streams: vec![vertex_buffer, instance_buffer],
+        },
+        bind_groups: vec![BindGroupId(0)],
+        push_constants: vec![],
+        targets: vec![Blend::Alpha.into()],
         shader,
         depth: None,
-        multisampled: false,
+        multisample: Multisample::default(),
+        primitive: Primitive {
+            cull_mode: CullMode::Front,
+            ..Default::default()
+        },
     };
 
     vec![pipeline]
@@ -176,12 +195,14 @@ fn pipelines() -> Vec<RenderPipeline> {
 
 fn passes() -> Vec<Pass> {
     let pass = Pass {
-        pipelines: vec![0],
+        name: Some("main".into()),
+        pipelines: vec![PipelineId(0)],
         color_attachments: vec![Attachment {
-            texture: 0,
+            texture: TextureId(0),
             resolve: None,
             clear: Some([0.0, 0.0, 0.0, 0.0]),
             store: true,
+            range: ViewRange::default(),
         }],
         depth_attachment: None,
     };
@@ -1,9 +1,10 @@
 mod common;
 
 use crate::common::{mesh, models, DrawDataStorage, Example};
+use common::models::{INDEX_BUFFER, INSTANCE_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use glam::Mat4;
-use odc_core::mdl::Size2d;
-use odc_core::{mdl::RenderModel, DrawData, OdcCore, BufferType};
+use odc_core::mdl::{PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl::RenderModel, DrawData, OdcCore};
 use std::f32::consts::PI;
 use std::time::Instant;
 use vp_cam::{Camera, CameraBuilder};
@@ -21,39 +22,46 @@ impl Example for InstancesExample {
         models::deferred::deferred_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
+        let model = Self::render_model();
+        let window = |texture: &str, name: &str| {
+            let texture = model.texture_by_name(texture).unwrap();
+            (texture, name.into(), Size2d { x: 800, y: 600 })
+        };
+
         vec![
-            (0, "position".into(), Size2d { x: 800, y: 600 }),
-            (1, "albedo".into(), Size2d { x: 800, y: 600 }),
-            (2, "light".into(), Size2d { x: 800, y: 600 }),
-            (4, "final".into(), Size2d { x: 800, y: 600 }),
+            window("gbuffer_position", "position"),
+            window("gbuffer_albedo", "albedo"),
+            window("light", "light"),
+            window("final", "final"),
         ]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let triangle_indices = [0, 1, 2];
-        renderer.write_buffer(BufferType::Index, &triangle_indices, 0);
+        renderer.write_buffer(INDEX_BUFFER, &triangle_indices, 0);
 
         let (vertex_data, index_data) = mesh::rectangle_mesh();
-        renderer.write_buffer(BufferType::Index, index_data, 3);
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 3);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
 
         let instance = Mat4::IDENTITY.to_cols_array_2d();
-        renderer.write_buffer(BufferType::Instance, &[instance], 0);
+        renderer.write_buffer(INSTANCE_BUFFER, &[instance], 0);
     }
 
     fn update(&mut self, renderer: &mut OdcCore) {
         let ident_transform = Mat4::IDENTITY.to_cols_array_2d();
         let world = ident_transform;
         let view_proj = self.0.view_proj_transform();
-        renderer.write_buffer(BufferType::Uniform, &[world, view_proj], 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[world, view_proj], 0);
 
         let angle = self.1.angle();
         let initial_pos = glam::vec4(0.8, 0.0, 0.0, 1.0);
         let light1_pos = glam::Mat4::from_rotation_z(angle) * initial_pos;
         let light2_pos = glam::Mat4::from_rotation_z(angle + 2.0 * PI / 3.0) * initial_pos;
         let light3_pos = glam::Mat4::from_rotation_z(angle + 4.0 * PI / 3.0) * initial_pos;
-        renderer.write_buffer(BufferType::Instance,
+        renderer.write_buffer(
+            INSTANCE_BUFFER,
             &[
                 light1_pos.to_array(),
                 light2_pos.to_array(),
@@ -64,39 +72,38 @@ impl Example for InstancesExample {
     }
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let rect = DrawData {
+        let rect = DrawData::Indexed {
             indices: 3..9,
             base_vertex: 0,
             instances: 0..1,
         };
 
-        let light = DrawData {
+        let light = DrawData::Indexed {
             indices: 0..3,
             base_vertex: 0,
             instances: 4..7,
         };
 
-        let tri = DrawData {
-            indices: 0..3,
-            base_vertex: 0,
+        let tri = DrawData::Vertices {
+            vertices: 0..3,
             instances: 0..1,
         };
 
         let deferred_data = DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![rect],
         };
 
         let light_data = DrawDataStorage {
-            pass: 1,
-            pipeline: 1,
+            pass: PassId(1),
+            pipeline: PipelineId(1),
             data: vec![light],
         };
 
         let final_data = DrawDataStorage {
-            pass: 2,
-            pipeline: 2,
+            pass: PassId(2),
+            pipeline: PipelineId(2),
             data: vec![tri],
         };
         vec![deferred_data, light_data, final_data]
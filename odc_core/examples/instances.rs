@@ -1,9 +1,10 @@
 mod common;
 
 use crate::common::{DrawDataStorage, Example};
+use common::models::{INDEX_BUFFER, INSTANCE_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use glam::Mat4;
-use odc_core::mdl::Size2d;
-use odc_core::{mdl::RenderModel, DrawData, OdcCore, BufferType};
+use odc_core::mdl::{PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl::RenderModel, DrawData, OdcCore};
 use std::f32::consts::PI;
 use std::time::Instant;
 use vp_cam::{Camera, CameraBuilder, Vec3};
@@ -21,17 +22,17 @@ impl Example for InstancesExample {
         common::models::color_mesh::color_mesh_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
-        vec![(0, "color".into(), Size2d { x: 800, y: 600 })]
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
+        vec![(TextureId(0), "color".into(), Size2d { x: 800, y: 600 })]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let (vertex_data, index_data) = common::mesh::triangle_mesh();
-        renderer.write_buffer(BufferType::Index, index_data, 0);
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 0);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
 
         let instances = get_instances();
-        renderer.write_buffer(BufferType::Instance, &instances, 0);
+        renderer.write_buffer(INSTANCE_BUFFER, &instances, 0);
     }
 
     fn update(&mut self, renderer: &mut OdcCore) {
@@ -39,19 +40,19 @@ impl Example for InstancesExample {
         let world = ident_transform;
         self.0.set_position(self.1.cam_position());
         let view_proj = self.0.view_proj_transform();
-        renderer.write_buffer(BufferType::Uniform, &[world, view_proj], 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[world, view_proj], 0);
     }
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let draw = DrawData {
+        let draw = DrawData::Indexed {
             indices: 0..3,
             base_vertex: 0,
             instances: 0..256,
         };
 
         vec![DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![draw],
         }]
     }
@@ -1,9 +1,10 @@
 mod common;
 
 use crate::common::{DrawDataStorage, Example};
+use common::models::{INDEX_BUFFER, INSTANCE_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use glam::{Mat4, Quat, Vec3};
-use odc_core::mdl::Size2d;
-use odc_core::{mdl::RenderModel, DrawData, OdcCore, BufferType};
+use odc_core::mdl::{PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl::RenderModel, DrawData, OdcCore};
 use std::f32::consts::PI;
 use vp_cam::{Camera, CameraBuilder};
 
@@ -18,23 +19,23 @@ impl Example for MeshesExample {
         common::models::color_mesh::color_mesh_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
         vec![
-            (0, "color".into(), Size2d { x: 800, y: 600 }),
-            (1, "depth".into(), Size2d { x: 800, y: 600 }),
+            (TextureId(0), "color".into(), Size2d { x: 800, y: 600 }),
+            (TextureId(1), "depth".into(), Size2d { x: 800, y: 600 }),
         ]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let (vertex_data, index_data) = common::mesh::triangle_mesh();
-        renderer.write_buffer(BufferType::Index, index_data, 0);
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 0);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
 
         let vertex_offset = vertex_data.len();
         let index_offset = index_data.len();
         let (vertex_data, index_data) = common::mesh::rectangle_mesh();
-        renderer.write_buffer(BufferType::Index, index_data, index_offset as _);
-        renderer.write_buffer(BufferType::Vertex, vertex_data, vertex_offset as _);
+        renderer.write_buffer(INDEX_BUFFER, index_data, index_offset as _);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, vertex_offset as _);
 
         let scale = Vec3::new(0.5, 0.5, 0.5);
         let left = Vec3::new(-0.2, 0.0, 0.2);
@@ -45,33 +46,33 @@ impl Example for MeshesExample {
             Mat4::from_scale_rotation_translation(scale, rot_left, left).to_cols_array_2d();
         let right_transform =
             Mat4::from_scale_rotation_translation(scale, rot_right, right).to_cols_array_2d();
-        renderer.write_buffer(BufferType::Instance, &[left_transform, right_transform], 0);
+        renderer.write_buffer(INSTANCE_BUFFER, &[left_transform, right_transform], 0);
 
         let ident_transform = Mat4::IDENTITY.to_cols_array_2d();
         let world = ident_transform;
         let camera = create_camera();
         let view_proj = camera.view_proj_transform();
-        renderer.write_buffer(BufferType::Uniform, &[world, view_proj], 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[world, view_proj], 0);
     }
 
     fn update(&mut self, _renderer: &mut OdcCore) {}
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let draw_triangle = DrawData {
+        let draw_triangle = DrawData::Indexed {
             indices: 0..3,
             base_vertex: 0,
             instances: 0..1,
         };
 
-        let draw_rectangle = DrawData {
+        let draw_rectangle = DrawData::Indexed {
             indices: 3..9,
             base_vertex: 3,
             instances: 1..2,
         };
 
         vec![DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![draw_triangle, draw_rectangle],
         }]
     }
@@ -1,10 +1,11 @@
 mod common;
 
 use crate::common::DrawDataStorage;
+use common::models::{INDEX_BUFFER, INSTANCE_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use common::{mesh, Example};
 use glam::{Mat4, Quat};
-use odc_core::mdl::Size2d;
-use odc_core::{mdl, mdl::RenderModel, DrawData, OdcCore, TextureData, TextureWrite, BufferType};
+use odc_core::mdl::{PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl, mdl::RenderModel, DrawData, OdcCore, TextureData, TextureWrite};
 use std::f32::consts::PI;
 use vp_cam::{Camera, CameraBuilder};
 
@@ -15,21 +16,21 @@ impl Example for MipMaps {
         common::models::mip_map::mip_map_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
-        vec![(0, "color".into(), Size2d { x: 800, y: 600 })]
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
+        vec![(TextureId(0), "color".into(), Size2d { x: 800, y: 600 })]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let (vertex_data, index_data) = mesh::sprite_mesh();
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
-        renderer.write_buffer(BufferType::Index, index_data, 0);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 0);
 
         let ident = Mat4::IDENTITY.to_cols_array_2d();
         let camera = create_camera();
-        renderer.write_buffer(BufferType::Uniform, &[ident, camera.view_proj_transform()], 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[ident, camera.view_proj_transform()], 0);
 
         let instance_data = instance_data();
-        renderer.write_buffer(BufferType::Instance, &instance_data, 0);
+        renderer.write_buffer(INSTANCE_BUFFER, &instance_data, 0);
 
         write_image(renderer);
     }
@@ -37,15 +38,15 @@ impl Example for MipMaps {
     fn update(&mut self, _renderer: &mut OdcCore) {}
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let draw = DrawData {
+        let draw = DrawData::Indexed {
             indices: 0..6,
             base_vertex: 0,
             instances: 0..7,
         };
 
         vec![DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![draw],
         }]
     }
@@ -71,7 +72,7 @@ fn write_image(renderer: &OdcCore) {
             size: size.into(),
         };
 
-        renderer.write_texture(1, write, layout);
+        renderer.write_texture(TextureId(1), write, layout);
     }
 }
 
@@ -1,10 +1,11 @@
 mod common;
 
 use crate::common::DrawDataStorage;
+use common::models::{INDEX_BUFFER, INSTANCE_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use common::{mesh, Example};
 use glam::Mat4;
-use odc_core::mdl::Size2d;
-use odc_core::{mdl::RenderModel, BufferType, DrawData, OdcCore};
+use odc_core::mdl::{PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl::RenderModel, DrawData, OdcCore};
 
 struct MsaaTriangle;
 
@@ -13,32 +14,32 @@ impl Example for MsaaTriangle {
         common::models::msaa_color_mesh::msaa_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
-        vec![(2, "color".into(), Size2d { x: 800, y: 600 })]
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
+        vec![(TextureId(2), "color".into(), Size2d { x: 800, y: 600 })]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let (vertex_data, index_data) = mesh::triangle_mesh();
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
-        renderer.write_buffer(BufferType::Index, index_data, 0);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 0);
 
         let ident = Mat4::IDENTITY.to_cols_array_2d();
-        renderer.write_buffer(BufferType::Uniform, &[ident, ident], 0);
-        renderer.write_buffer(BufferType::Instance, &[ident], 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[ident, ident], 0);
+        renderer.write_buffer(INSTANCE_BUFFER, &[ident], 0);
     }
 
     fn update(&mut self, _renderer: &mut OdcCore) {}
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let draw = DrawData {
+        let draw = DrawData::Indexed {
             indices: 0..3,
             base_vertex: 0,
             instances: 0..1,
         };
 
         vec![DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![draw],
         }]
     }
@@ -1,11 +1,12 @@
 mod common;
 
 use crate::common::DrawDataStorage;
+use common::models::{INDEX_BUFFER, INSTANCE_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use common::{mesh, Example};
 use glam::{Mat4, Quat};
 use image::{EncodableLayout, ImageFormat};
-use odc_core::mdl::Size2d;
-use odc_core::{mdl, mdl::RenderModel, BufferType, DrawData, OdcCore, TextureData, TextureWrite};
+use odc_core::mdl::{PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl, mdl::RenderModel, DrawData, OdcCore, TextureData, TextureWrite};
 use std::fs;
 use std::io::BufReader;
 use std::path::Path;
@@ -23,17 +24,17 @@ impl Example for Replacing {
         common::models::sprites::sprites_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
-        vec![(0, "color".into(), Size2d { x: 800, y: 600 })]
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
+        vec![(TextureId(0), "color".into(), Size2d { x: 800, y: 600 })]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let (vertex_data, index_data) = mesh::sprite_mesh();
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
-        renderer.write_buffer(BufferType::Index, index_data, 0);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 0);
 
-        renderer.insert_stock_texture(1, "sprite".into(), None);
-        renderer.insert_stock_buffer(BufferType::Uniform, "uniform".into(), None);
+        renderer.insert_stock_texture(TextureId(1), "sprite".into(), None);
+        renderer.insert_stock_buffer(UNIFORM_BUFFER, "uniform".into(), None);
 
         let instance_transform = Mat4::from_scale_rotation_translation(
             (0.5, 0.5, 1.0).into(),
@@ -46,11 +47,11 @@ impl Example for Replacing {
         let mut instance_data: Vec<[f32; 4]> = Vec::new();
         instance_data.extend_from_slice(&instance_transform);
         instance_data.push(planet_uv_offset_scale);
-        renderer.write_buffer(BufferType::Instance, &instance_data, 0);
+        renderer.write_buffer(INSTANCE_BUFFER, &instance_data, 0);
 
         let ident = Mat4::IDENTITY.to_cols_array_2d();
         let world = Mat4::from_translation(glam::vec3(-0.25, 0.0, 0.0)).to_cols_array_2d();
-        renderer.write_buffer(BufferType::Uniform, &[world, ident], 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[world, ident], 0);
 
         let world = Mat4::from_translation(glam::vec3(0.25, 0.0, 0.0)).to_cols_array_2d();
         renderer.write_stock_buffer("uniform", &[world, ident], 0);
@@ -69,15 +70,15 @@ impl Example for Replacing {
     }
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let draw = DrawData {
+        let draw = DrawData::Indexed {
             indices: 0..6,
             base_vertex: 0,
             instances: 0..1,
         };
 
         vec![DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![draw],
         }]
     }
@@ -108,7 +109,7 @@ fn write_images(renderer: &OdcCore) {
         rows_per_layer: 0,
     };
 
-    renderer.write_texture(1, write, data);
+    renderer.write_texture(TextureId(1), write, data);
 
     let data = load_image("odc_core/examples/data/black_hole.png");
 
@@ -1,11 +1,12 @@
 mod common;
 
 use crate::common::DrawDataStorage;
+use common::models::{INDEX_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use common::{mesh, Example};
 use glam::{Mat3, Mat4};
 use image::{EncodableLayout, ImageFormat};
-use odc_core::mdl::{Extent3d, Size2d};
-use odc_core::{mdl, mdl::RenderModel, DrawData, OdcCore, TextureData, TextureWrite, BufferType};
+use odc_core::mdl::{Extent3d, PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl, mdl::RenderModel, DrawData, OdcCore, TextureData, TextureWrite};
 use std::f32::consts::PI;
 use std::fs;
 use std::io::BufReader;
@@ -20,14 +21,14 @@ impl Example for Skybox {
         common::models::skybox::skybox_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
-        vec![(0, "color".into(), Size2d { x: 800, y: 600 })]
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
+        vec![(TextureId(0), "color".into(), Size2d { x: 800, y: 600 })]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let (vertex_data, index_data) = mesh::skybox_mesh();
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
-        renderer.write_buffer(BufferType::Index, index_data, 0);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 0);
 
         write_skybox(renderer);
     }
@@ -36,19 +37,19 @@ impl Example for Skybox {
         let angle = self.0.angle();
         let camera = create_camera(angle);
         let ident = Mat4::IDENTITY.to_cols_array_2d();
-        renderer.write_buffer(BufferType::Uniform, &[ident, camera.view_proj_transform()], 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[ident, camera.view_proj_transform()], 0);
     }
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let draw = DrawData {
+        let draw = DrawData::Indexed {
             indices: 0..36,
             base_vertex: 0,
             instances: 0..1,
         };
 
         vec![DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![draw],
         }]
     }
@@ -74,7 +75,7 @@ fn write_skybox(renderer: &OdcCore) {
         rows_per_layer: size.height,
     };
 
-    renderer.write_texture(1, write, data)
+    renderer.write_texture(TextureId(1), write, data)
 }
 
 fn load_image<P: AsRef<Path>>(path: P) -> Vec<u8> {
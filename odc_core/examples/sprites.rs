@@ -1,11 +1,12 @@
 mod common;
 
 use crate::common::DrawDataStorage;
+use common::models::{INDEX_BUFFER, INSTANCE_BUFFER, UNIFORM_BUFFER, VERTEX_BUFFER};
 use common::{mesh, Example};
 use glam::{Mat4, Quat};
 use image::{EncodableLayout, ImageFormat};
-use odc_core::mdl::Size2d;
-use odc_core::{mdl, mdl::RenderModel, DrawData, OdcCore, TextureData, TextureWrite, BufferType};
+use odc_core::mdl::{PassId, PipelineId, Size2d, TextureId};
+use odc_core::{mdl, mdl::RenderModel, DrawData, OdcCore, TextureData, TextureWrite};
 use std::fs;
 use std::io::BufReader;
 use std::path::Path;
@@ -17,14 +18,14 @@ impl Example for Sprite {
         common::models::sprites::sprites_model()
     }
 
-    fn windows() -> Vec<(usize, String, Size2d)> {
-        vec![(0, "color".into(), Size2d { x: 800, y: 600 })]
+    fn windows() -> Vec<(TextureId, String, Size2d)> {
+        vec![(TextureId(0), "color".into(), Size2d { x: 800, y: 600 })]
     }
 
     fn init(&mut self, renderer: &mut OdcCore) {
         let (vertex_data, index_data) = mesh::sprite_mesh();
-        renderer.write_buffer(BufferType::Vertex, vertex_data, 0);
-        renderer.write_buffer(BufferType::Index, index_data, 0);
+        renderer.write_buffer(VERTEX_BUFFER, vertex_data, 0);
+        renderer.write_buffer(INDEX_BUFFER, index_data, 0);
 
         let planet_transform = Mat4::from_scale_rotation_translation(
             (0.5, 0.5, 1.0).into(),
@@ -48,8 +49,8 @@ impl Example for Sprite {
         instance_data.push(planet_uv_offset_scale);
 
         let ident = Mat4::IDENTITY.to_cols_array_2d();
-        renderer.write_buffer(BufferType::Uniform, &[ident, ident], 0);
-        renderer.write_buffer(BufferType::Instance, &instance_data, 0);
+        renderer.write_buffer(UNIFORM_BUFFER, &[ident, ident], 0);
+        renderer.write_buffer(INSTANCE_BUFFER, &instance_data, 0);
 
         write_images(renderer);
     }
@@ -57,15 +58,15 @@ impl Example for Sprite {
     fn update(&mut self, _renderer: &mut OdcCore) {}
 
     fn draw_data(&self) -> Vec<DrawDataStorage> {
-        let draw = DrawData {
+        let draw = DrawData::Indexed {
             indices: 0..6,
             base_vertex: 0,
             instances: 0..2,
         };
 
         vec![DrawDataStorage {
-            pass: 0,
-            pipeline: 0,
+            pass: PassId(0),
+            pipeline: PipelineId(0),
             data: vec![draw],
         }]
     }
@@ -87,7 +88,7 @@ fn write_images(renderer: &OdcCore) {
         rows_per_layer: 0,
     };
 
-    renderer.write_texture(1, write, data);
+    renderer.write_texture(TextureId(1), write, data);
 
     let data = load_image("odc_core/examples/data/black_hole.png");
 
@@ -103,7 +104,7 @@ fn write_images(renderer: &OdcCore) {
         rows_per_layer: 0,
     };
 
-    renderer.write_texture(1, write, data);
+    renderer.write_texture(TextureId(1), write, data);
 }
 
 fn load_image<P: AsRef<Path>>(path: P) -> Vec<u8> {
@@ -0,0 +1,341 @@
+use crate::mdl;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+pub type OdcResult<T> = Result<T, OdcError>;
+
+#[derive(Debug)]
+pub enum OdcError {
+    InvalidModel(Vec<mdl::ValidationError>),
+    AdapterNotFound,
+    UnsupportedFeatures(wgpu::Features),
+    RequestDevice(wgpu::RequestDeviceError),
+    SurfaceFormatNotFound,
+    ShaderNotFound {
+        path: PathBuf,
+        source: io::Error,
+    },
+    PipelineNotUsed(mdl::PipelineId),
+    TextureNotFound(mdl::TextureId),
+    TextureNotWritable(mdl::TextureId),
+    MipLevelNotFound {
+        texture: mdl::TextureId,
+        mip_level: u32,
+    },
+    TextureWriteOutOfBounds(mdl::TextureId),
+    TextureWriteNotBlockAligned(mdl::TextureId),
+    TextureDataLayoutInvalid(mdl::TextureId),
+    TextureDataTooShort {
+        texture: mdl::TextureId,
+        size: usize,
+        required: u64,
+    },
+    MipmapsNotSupported(mdl::TextureId),
+    BufferOverflow {
+        end: u64,
+        size: u64,
+    },
+    UnalignedBufferWrite {
+        offset: u64,
+        size: u64,
+    },
+    BufferNotFound(mdl::BufferId),
+    BufferNotIndirect(mdl::BufferId),
+    IndirectOutOfBounds {
+        buffer: mdl::BufferId,
+        offset: u64,
+        count: u32,
+    },
+    PipelineWithoutIndexBuffer(mdl::PipelineId),
+    PassNotFound(mdl::PassId),
+    PipelineNotFound(mdl::PipelineId),
+    ComputePipelineNotFound(mdl::ComputePipelineId),
+    PipelineNotInPass {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+    },
+    ViewportEmpty {
+        width: f32,
+        height: f32,
+    },
+    ViewportDepthOutOfRange {
+        min_depth: f32,
+        max_depth: f32,
+    },
+    ScissorEmpty(crate::Scissor),
+    ScissorOutOfBounds {
+        scissor: crate::Scissor,
+        size: mdl::Size2d,
+    },
+    BindGroupNotFound(mdl::BindGroupId),
+    BindGroupIndexOutOfRange {
+        pipeline: mdl::PipelineId,
+        index: u32,
+    },
+    BindGroupIncompatible {
+        pipeline: mdl::PipelineId,
+        index: u32,
+        bind_group: mdl::BindGroupId,
+    },
+    DynamicOffsetsMismatch {
+        bind_group: mdl::BindGroupId,
+        expected: usize,
+        actual: usize,
+    },
+    UnalignedDynamicOffset {
+        bind_group: mdl::BindGroupId,
+        binding: u32,
+        offset: u32,
+        alignment: u32,
+    },
+    DynamicOffsetOutOfBounds {
+        bind_group: mdl::BindGroupId,
+        binding: u32,
+        offset: u32,
+    },
+    PushConstantsTooLarge {
+        size: u32,
+        max: u32,
+    },
+    UnalignedPushConstants {
+        offset: u32,
+        size: usize,
+    },
+    PushConstantsOutOfRange {
+        pipeline: mdl::PipelineId,
+        offset: u32,
+        size: usize,
+    },
+    StockBufferNotFound(String),
+    StockTextureNotFound(String),
+    StockAttachmentResize(mdl::TextureId),
+    ModelFile {
+        path: PathBuf,
+        source: io::Error,
+    },
+    #[cfg(feature = "serde")]
+    Ron(ron::Error),
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for OdcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidModel(errors) => {
+                write!(f, "invalid render model:")?;
+                for error in errors {
+                    write!(f, "\n  {}", error)?;
+                }
+                Ok(())
+            }
+            Self::AdapterNotFound => write!(f, "can't find suit graphics adapter"),
+            Self::UnsupportedFeatures(features) => write!(
+                f,
+                "graphics adapter doesn't support required features: {:?}",
+                features
+            ),
+            Self::RequestDevice(e) => write!(f, "can't create graphics device: {}", e),
+            Self::SurfaceFormatNotFound => write!(f, "can't find suit surface format"),
+            Self::ShaderNotFound { path, source } => {
+                write!(f, "can't read shader file {:?}: {}", path, source)
+            }
+            Self::PipelineNotUsed(pipeline) => {
+                write!(f, "pipeline {} is not used in any pass", pipeline.0)
+            }
+            Self::TextureNotFound(texture) => write!(f, "texture {} not found", texture.0),
+            Self::TextureNotWritable(texture) => {
+                write!(f, "texture {} is not writable", texture.0)
+            }
+            Self::MipLevelNotFound { texture, mip_level } => {
+                write!(f, "texture {} has no mip level {}", texture.0, mip_level)
+            }
+            Self::TextureWriteOutOfBounds(texture) => write!(
+                f,
+                "texture write exceeds bounds of texture {} mip level",
+                texture.0
+            ),
+            Self::TextureWriteNotBlockAligned(texture) => write!(
+                f,
+                "texture write region is not aligned to blocks of texture {}",
+                texture.0
+            ),
+            Self::TextureDataLayoutInvalid(texture) => write!(
+                f,
+                "rows or layers of data overlap in texture {} write",
+                texture.0
+            ),
+            Self::TextureDataTooShort {
+                texture,
+                size,
+                required,
+            } => write!(
+                f,
+                "texture {} write requires {} bytes of data, but {} provided",
+                texture.0, required, size
+            ),
+            Self::MipmapsNotSupported(texture) => {
+                write!(f, "can't generate mip levels of texture {}", texture.0)
+            }
+            Self::BufferOverflow { end, size } => write!(
+                f,
+                "buffer write ends at {}, but buffer size is {}",
+                end, size
+            ),
+            Self::UnalignedBufferWrite { offset, size } => write!(
+                f,
+                "buffer write offset {} and size {} must be multiples of {}",
+                offset,
+                size,
+                wgpu::COPY_BUFFER_ALIGNMENT
+            ),
+            Self::BufferNotFound(buffer) => write!(f, "buffer {} not found", buffer.0),
+            Self::BufferNotIndirect(buffer) => {
+                write!(f, "buffer {} doesn't have indirect usage", buffer.0)
+            }
+            Self::IndirectOutOfBounds {
+                buffer,
+                offset,
+                count,
+            } => write!(
+                f,
+                "{} indirect draws at offset {} exceed buffer {}",
+                count, offset, buffer.0
+            ),
+            Self::PipelineWithoutIndexBuffer(pipeline) => {
+                write!(
+                    f,
+                    "pipeline {} has no index buffer for indexed draw",
+                    pipeline.0
+                )
+            }
+            Self::PassNotFound(pass) => write!(f, "pass {} not found", pass.0),
+            Self::PipelineNotFound(pipeline) => write!(f, "pipeline {} not found", pipeline.0),
+            Self::ComputePipelineNotFound(pipeline) => {
+                write!(f, "compute pipeline {} not found", pipeline.0)
+            }
+            Self::PipelineNotInPass { pipeline, pass } => {
+                write!(f, "pipeline {} is not part of pass {}", pipeline.0, pass.0)
+            }
+            Self::ViewportEmpty { width, height } => {
+                write!(f, "viewport size {}x{} is not positive", width, height)
+            }
+            Self::ViewportDepthOutOfRange {
+                min_depth,
+                max_depth,
+            } => write!(
+                f,
+                "viewport depth range {}..{} is not ordered range within 0..1",
+                min_depth, max_depth
+            ),
+            Self::ScissorEmpty(scissor) => write!(f, "scissor {:?} is empty", scissor),
+            Self::ScissorOutOfBounds { scissor, size } => write!(
+                f,
+                "scissor {:?} exceeds pass target size {}x{}",
+                scissor, size.x, size.y
+            ),
+            Self::BindGroupNotFound(bind_group) => {
+                write!(f, "bind group {} not found", bind_group.0)
+            }
+            Self::BindGroupIndexOutOfRange { pipeline, index } => write!(
+                f,
+                "pipeline {} has no bind group at index {}",
+                pipeline.0, index
+            ),
+            Self::BindGroupIncompatible {
+                pipeline,
+                index,
+                bind_group,
+            } => write!(
+                f,
+                "bind group {} is incompatible with bind group {} of pipeline {}",
+                bind_group.0, index, pipeline.0
+            ),
+            Self::DynamicOffsetsMismatch {
+                bind_group,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "bind group {} expects {} dynamic offsets, but {} provided",
+                bind_group.0, expected, actual
+            ),
+            Self::UnalignedDynamicOffset {
+                bind_group,
+                binding,
+                offset,
+                alignment,
+            } => write!(
+                f,
+                "dynamic offset {} of binding {} in bind group {} must be multiple of {}",
+                offset, binding, bind_group.0, alignment
+            ),
+            Self::DynamicOffsetOutOfBounds {
+                bind_group,
+                binding,
+                offset,
+            } => write!(
+                f,
+                "binding {} of bind group {} with dynamic offset {} exceeds its buffer",
+                binding, bind_group.0, offset
+            ),
+            Self::PushConstantsTooLarge { size, max } => write!(
+                f,
+                "push constants of {} bytes exceed adapter limit of {} bytes",
+                size, max
+            ),
+            Self::UnalignedPushConstants { offset, size } => write!(
+                f,
+                "push constants offset {} and size {} must be multiples of {}",
+                offset,
+                size,
+                wgpu::PUSH_CONSTANT_ALIGNMENT
+            ),
+            Self::PushConstantsOutOfRange {
+                pipeline,
+                offset,
+                size,
+            } => write!(
+                f,
+                "push constants at offset {} with size {} don't match ranges of pipeline {}",
+                offset, size, pipeline.0
+            ),
+            Self::StockBufferNotFound(name) => write!(f, "stock buffer {:?} not found", name),
+            Self::StockTextureNotFound(name) => write!(f, "stock texture {:?} not found", name),
+            Self::StockAttachmentResize(texture) => write!(
+                f,
+                "can't create resized stock texture for attachment texture {}",
+                texture.0
+            ),
+            Self::ModelFile { path, source } => {
+                write!(f, "can't access model file {:?}: {}", path, source)
+            }
+            #[cfg(feature = "serde")]
+            Self::Ron(e) => write!(f, "invalid RON model: {}", e),
+            #[cfg(feature = "serde")]
+            Self::Json(e) => write!(f, "invalid JSON model: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OdcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::RequestDevice(e) => Some(e),
+            Self::ShaderNotFound { source, .. } => Some(source),
+            Self::ModelFile { source, .. } => Some(source),
+            #[cfg(feature = "serde")]
+            Self::Ron(e) => Some(e),
+            #[cfg(feature = "serde")]
+            Self::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for OdcError {
+    fn from(e: wgpu::RequestDeviceError) -> Self {
+        Self::RequestDevice(e)
+    }
+}
@@ -1,40 +1,79 @@
+use crate::{OdcError, OdcResult};
 use wgpu::{
-    Adapter, Device, DeviceDescriptor, Instance, Limits, Queue, RequestAdapterOptions, Surface,
+    Adapter, Device, DeviceDescriptor, Features, Instance, Limits, Queue, RequestAdapterOptions,
+    Surface,
 };
 
 pub struct GfxDevice {
     pub adapter: Adapter,
     pub device: Device,
     pub queue: Queue,
+    /// Features enabled on `device`.
+    pub features: Features,
+    /// Limits of `device`.
+    pub limits: Limits,
 }
 
 impl GfxDevice {
-    pub fn new(instance: &Instance, surface: Option<&Surface>) -> Self {
-        let adapter = Self::request_adapter(instance, surface);
-        let (device, queue) = Self::request_device(&adapter);
-        Self {
+    /// Features, which are enabled if adapter supports them.
+    pub const OPTIONAL_FEATURES: Features = Features::MULTI_DRAW_INDIRECT;
+
+    pub fn new(
+        instance: &Instance,
+        surface: Option<&Surface>,
+        features: Features,
+        push_constants_size: u32,
+    ) -> OdcResult<Self> {
+        let adapter = Self::request_adapter(instance, surface)?;
+        let (device, queue) = Self::request_device(&adapter, features, push_constants_size)?;
+        let features = device.features();
+        let limits = device.limits();
+        Ok(Self {
             adapter,
             device,
             queue,
-        }
+            features,
+            limits,
+        })
     }
 
-    fn request_adapter(instance: &Instance, surface: Option<&Surface>) -> Adapter {
+    fn request_adapter(instance: &Instance, surface: Option<&Surface>) -> OdcResult<Adapter> {
         let options = RequestAdapterOptions {
             compatible_surface: surface,
             ..Default::default()
         };
         let adapter_fut = instance.request_adapter(&options);
-        pollster::block_on(adapter_fut).unwrap()
+        pollster::block_on(adapter_fut).ok_or(OdcError::AdapterNotFound)
     }
 
-    fn request_device(adapter: &Adapter) -> (Device, Queue) {
-        let limits = Limits::downlevel_defaults().using_resolution(adapter.limits());
+    fn request_device(
+        adapter: &Adapter,
+        features: Features,
+        push_constants_size: u32,
+    ) -> OdcResult<(Device, Queue)> {
+        let unsupported = features - adapter.features();
+        if !unsupported.is_empty() {
+            return Err(OdcError::UnsupportedFeatures(unsupported));
+        }
+
+        let max_push_constants_size = adapter.limits().max_push_constant_size;
+        if push_constants_size > max_push_constants_size {
+            return Err(OdcError::PushConstantsTooLarge {
+                size: push_constants_size,
+                max: max_push_constants_size,
+            });
+        }
+
+        let mut limits = Limits::downlevel_defaults().using_resolution(adapter.limits());
+        if features.contains(Features::PUSH_CONSTANTS) {
+            limits.max_push_constant_size = max_push_constants_size;
+        }
         let descriptor = DeviceDescriptor {
+            features: features | (adapter.features() & Self::OPTIONAL_FEATURES),
             limits,
             ..Default::default()
         };
         let device_fut = adapter.request_device(&descriptor, None);
-        pollster::block_on(device_fut).unwrap()
+        Ok(pollster::block_on(device_fut)?)
     }
 }
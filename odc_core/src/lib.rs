@@ -1,11 +1,12 @@
+pub use crate::error::{OdcError, OdcResult};
 use crate::gdevice::GfxDevice;
 use crate::mdl_parse::ModelParser;
+use crate::mipmaps::MipmapGenerator;
 use crate::pipelines::PipelinesFactory;
-use crate::res::{BindGroupFactory, BindGroups, Buffers, ResourceFactory, Resources, TextureInfo};
+use crate::res::{BindGroupFactory, BindGroups, ResourceFactory, Resources, TextureInfo};
 use bytemuck::Pod;
 use pipelines::Pipelines;
 use raw_window_handle::HasRawWindowHandle;
-pub use res::BufferType;
 use std::collections::{HashMap, HashSet};
 use std::mem;
 use std::num::NonZeroU32;
@@ -16,9 +17,15 @@ use window::Window;
 pub use window::WindowInfo;
 use window::WindowSource;
 
+mod error;
 mod gdevice;
 pub mod mdl;
+mod mdl_builder;
+#[cfg(feature = "serde")]
+mod mdl_file;
 mod mdl_parse;
+mod mdl_validate;
+mod mipmaps;
 mod pipelines;
 mod res;
 mod swapchain;
@@ -30,81 +37,127 @@ pub struct OdcCore {
     resources: Resources,
     bind_groups: BindGroups,
     pipelines: Pipelines,
+    mipmaps: MipmapGenerator,
     model: mdl::RenderModel,
     windows: HashMap<String, Window>,
-    texture_windows: HashMap<usize, HashSet<String>>,
+    texture_windows: HashMap<mdl::TextureId, HashSet<String>>,
 }
 
 impl OdcCore {
     pub fn new(model: mdl::RenderModel) -> Self {
-        let instance = Instance::new(Backends::all());
-        let device = GfxDevice::new(&instance, None);
-        let parser = ModelParser::new(&model);
-        let resources = Self::create_resources(&device.device, &parser);
-        let bind_groups = Self::create_bind_groups(&device.device, &parser, &resources);
-        let pipelines = Self::create_pipelines(&device.device, &parser, &bind_groups);
+        Self::try_new(model).unwrap()
+    }
 
-        Self {
-            instance,
-            device,
-            resources,
-            bind_groups,
-            pipelines,
-            model,
-            windows: Default::default(),
-            texture_windows: Default::default(),
-        }
+    /// Same as `new`, but validates `model` first and returns error instead of panic.
+    pub fn try_new(model: mdl::RenderModel) -> OdcResult<Self> {
+        Self::validate_model(&model)?;
+
+        let parser = ModelParser::new(&model);
+        let features = parser.required_features();
+        let push_constants_size = parser.push_constants_size();
+        let instance = Instance::new(Backends::all());
+        let device = GfxDevice::new(&instance, None, features, push_constants_size)?;
+        Self::with_device(instance, device, model)
     }
 
     pub fn with_window_support(model: mdl::RenderModel, window: &impl HasRawWindowHandle) -> Self {
+        Self::try_with_window_support(model, window).unwrap()
+    }
+
+    pub fn try_with_window_support(
+        model: mdl::RenderModel,
+        window: &impl HasRawWindowHandle,
+    ) -> OdcResult<Self> {
+        Self::validate_model(&model)?;
+
+        let parser = ModelParser::new(&model);
+        let features = parser.required_features();
+        let push_constants_size = parser.push_constants_size();
         let instance = Instance::new(Backends::all());
         let surface = unsafe { instance.create_surface(window) };
-        let device = GfxDevice::new(&instance, Some(&surface));
+        let device = GfxDevice::new(&instance, Some(&surface), features, push_constants_size)?;
+        Self::with_device(instance, device, model)
+    }
+
+    fn validate_model(model: &mdl::RenderModel) -> OdcResult<()> {
+        let errors = model.validate();
+        if !errors.is_empty() {
+            return Err(OdcError::InvalidModel(errors));
+        }
+        Ok(())
+    }
+
+    fn with_device(
+        instance: wgpu::Instance,
+        device: GfxDevice,
+        model: mdl::RenderModel,
+    ) -> OdcResult<Self> {
         let parser = ModelParser::new(&model);
         let resources = Self::create_resources(&device.device, &parser);
         let bind_groups = Self::create_bind_groups(&device.device, &parser, &resources);
-        let pipelines = Self::create_pipelines(&device.device, &parser, &bind_groups);
+        let pipelines = Self::create_pipelines(&device.device, &parser, &bind_groups)?;
+        let mipmaps = MipmapGenerator::new(&device.device, parser.mipmap_formats());
 
-        Self {
+        Ok(Self {
             instance,
             device,
             resources,
             bind_groups,
             pipelines,
+            mipmaps,
             model,
             windows: Default::default(),
             texture_windows: Default::default(),
-        }
+        })
     }
 
     /// # Safety
-    /// Handle `window` MUST stay valid until `remove_window` call with same `source_texture_id`.
+    /// Handle `window` MUST stay valid until `remove_window` call with same `source`.
     pub unsafe fn add_window<Handle>(
         &mut self,
-        source_index: usize,
+        source: mdl::TextureId,
         window_info: WindowInfo<Handle>,
     ) where
         Handle: HasRawWindowHandle,
     {
+        self.try_add_window(source, window_info).unwrap()
+    }
+
+    /// # Safety
+    /// Handle `window` MUST stay valid until `remove_window` call with same `source`.
+    pub unsafe fn try_add_window<Handle>(
+        &mut self,
+        source: mdl::TextureId,
+        window_info: WindowInfo<Handle>,
+    ) -> OdcResult<()>
+    where
+        Handle: HasRawWindowHandle,
+    {
+        let source_texture = self
+            .resources
+            .textures
+            .get(source.0)
+            .ok_or(OdcError::TextureNotFound(source))?;
+
         let surface = self.instance.create_surface(&window_info.handle);
-        let swapchain = Swapchain::new(surface, &self.device.adapter);
+        let swapchain = Swapchain::new(surface, &self.device.adapter)?;
         swapchain.resize(&self.device.device, window_info.size);
 
-        let source_texture = &self.resources.textures[source_index];
-        let texture_view = source_texture.create_view(None);
+        let texture_view = source_texture.create_view(None, &Default::default());
         let format = source_texture.info.format;
-        let source = WindowSource {
+        let window_source = WindowSource {
             texture_view,
             format,
         };
 
-        let window = Window::new(&self.device.device, swapchain, source);
+        let window = Window::new(&self.device.device, swapchain, window_source);
 
         self.windows.insert(window_info.name.to_string(), window);
         self.texture_windows
-            .entry(source_index)
+            .entry(source)
             .or_default()
             .insert(window_info.name.to_string());
+        Ok(())
     }
 
     pub fn remove_window(&mut self, name: &str) {
@@ -121,7 +174,7 @@ impl OdcCore {
         self.windows[window_name].resize(&self.device.device, size)
     }
 
-    pub fn resize_attachments(&mut self, attachment: usize, size: mdl::Size2d) {
+    pub fn resize_attachments(&mut self, attachment: mdl::TextureId, size: mdl::Size2d) {
         if size.is_zero() {
             return;
         }
@@ -130,32 +183,25 @@ impl OdcCore {
         let factory = ResourceFactory::new(&self.device.device);
 
         let mut broken_bind_groups = HashSet::new();
-        for texture_index in to_resize {
-            broken_bind_groups.extend(self.model.bind_groups.iter().enumerate().filter_map(
-                |(i, bg)| {
-                    if bg.has_texture(texture_index) {
-                        Some(i)
-                    } else {
-                        None
-                    }
-                },
-            ));
+        for texture_id in to_resize {
+            broken_bind_groups.extend(self.model.texture_bind_groups(texture_id));
 
+            let info = &self.resources.textures[texture_id.0].info;
             let size = wgpu::Extent3d {
                 width: size.x as _,
                 height: size.y as _,
-                depth_or_array_layers: 1,
+                depth_or_array_layers: info.size.depth_or_array_layers,
             };
 
             let info = TextureInfo {
                 size,
-                ..self.resources.textures[texture_index].info
+                ..info.clone()
             };
-            self.resources.textures[texture_index] = factory.create_texture(info);
-            if let Some(windows) = self.texture_windows.get(&texture_index) {
+            self.resources.textures[texture_id.0] = factory.create_texture(info);
+            if let Some(windows) = self.texture_windows.get(&texture_id) {
                 for window in windows.iter() {
-                    let texture = &self.resources.textures[texture_index];
-                    let source_view = texture.create_view(None);
+                    let texture = &self.resources.textures[texture_id.0];
+                    let source_view = texture.create_view(None, &Default::default());
                     let window = self.windows.get_mut(window).unwrap();
                     window.refresh_bind_group(&self.device.device, &source_view);
                 }
@@ -163,40 +209,170 @@ impl OdcCore {
         }
 
         let factory = BindGroupFactory::new(&self.device.device, &self.resources);
-        for bind_group_index in broken_bind_groups {
-            factory.refresh_bind_group(&mut self.bind_groups.0[bind_group_index]);
+        for bind_group_id in broken_bind_groups {
+            factory.refresh_bind_group(&mut self.bind_groups.0[bind_group_id.0]);
         }
     }
 
-    pub fn write_buffer<T: Pod>(&self, typ: BufferType, data: &[T], offset: u64) {
-        let buffer = self.resources.buffers.get(typ);
-        self.write_buffer_inner(&buffer.handle, data, offset)
+    pub fn write_buffer<T: Pod>(&self, id: mdl::BufferId, data: &[T], offset: u64) {
+        self.try_write_buffer(id, data, offset).unwrap()
+    }
+
+    pub fn try_write_buffer<T: Pod>(
+        &self,
+        id: mdl::BufferId,
+        data: &[T],
+        offset: u64,
+    ) -> OdcResult<()> {
+        let buffer = self
+            .resources
+            .buffers
+            .get(id.0)
+            .ok_or(OdcError::BufferNotFound(id))?;
+        self.write_buffer_inner(buffer, data, offset)
     }
 
     pub fn write_stock_buffer<T: Pod>(&self, name: &str, data: &[T], offset: u64) {
-        let (_, buffer) = self.resources.stock.buffer(name);
-        self.write_buffer_inner(&buffer.handle, data, offset)
+        self.try_write_stock_buffer(name, data, offset).unwrap()
+    }
+
+    pub fn try_write_stock_buffer<T: Pod>(
+        &self,
+        name: &str,
+        data: &[T],
+        offset: u64,
+    ) -> OdcResult<()> {
+        let (_, buffer) = self.resources.stock.buffer(name)?;
+        self.write_buffer_inner(buffer, data, offset)
+    }
+
+    fn write_buffer_inner<T: Pod>(
+        &self,
+        buffer: &res::Buffer,
+        data: &[T],
+        offset: u64,
+    ) -> OdcResult<()> {
+        let data: &[u8] = bytemuck::cast_slice(data);
+        let size = data.len() as u64;
+
+        // Overflowing end is reported as the largest possible one.
+        let end = (mem::size_of::<T>() as u64)
+            .checked_mul(offset)
+            .and_then(|offset| offset.checked_add(size))
+            .unwrap_or(u64::MAX);
+        if end > buffer.info.size {
+            return Err(OdcError::BufferOverflow {
+                end,
+                size: buffer.info.size,
+            });
+        }
+        let offset = end - size;
+
+        let alignment = wgpu::COPY_BUFFER_ALIGNMENT;
+        if offset % alignment != 0 || size % alignment != 0 {
+            return Err(OdcError::UnalignedBufferWrite { offset, size });
+        }
+
+        self.device.queue.write_buffer(&buffer.handle, offset, data);
+        Ok(())
     }
 
-    fn write_buffer_inner<T: Pod>(&self, buffer: &wgpu::Buffer, data: &[T], offset: u64) {
-        let data = bytemuck::cast_slice(data);
-        let offset = mem::size_of::<T>() as u64 * offset;
-        self.device.queue.write_buffer(buffer, offset, data);
+    pub fn write_texture(&self, id: mdl::TextureId, write: TextureWrite, data: TextureData) {
+        self.try_write_texture(id, write, data).unwrap()
     }
 
-    pub fn write_texture(&self, index: usize, write: TextureWrite, data: TextureData) {
-        let texture = &self.resources.textures[index];
-        self.write_texture_inner(&texture.handle, write, data)
+    pub fn try_write_texture(
+        &self,
+        id: mdl::TextureId,
+        write: TextureWrite,
+        data: TextureData,
+    ) -> OdcResult<()> {
+        let texture = self
+            .resources
+            .textures
+            .get(id.0)
+            .ok_or(OdcError::TextureNotFound(id))?;
+        self.write_texture_inner(id, texture, write, data)
     }
 
     pub fn write_stock_texture(&self, name: &str, write: TextureWrite, data: TextureData) {
-        let (_, texture) = self.resources.stock.texture(name);
-        self.write_texture_inner(&texture.handle, write, data)
+        self.try_write_stock_texture(name, write, data).unwrap()
     }
 
-    fn write_texture_inner(&self, texture: &wgpu::Texture, write: TextureWrite, data: TextureData) {
+    pub fn try_write_stock_texture(
+        &self,
+        name: &str,
+        write: TextureWrite,
+        data: TextureData,
+    ) -> OdcResult<()> {
+        let (id, texture) = self.resources.stock.texture(name)?;
+        self.write_texture_inner(*id, texture, write, data)
+    }
+
+    fn write_texture_inner(
+        &self,
+        id: mdl::TextureId,
+        texture: &res::Texture,
+        write: TextureWrite,
+        data: TextureData,
+    ) -> OdcResult<()> {
+        if !texture.info.usages.contains(wgpu::TextureUsages::COPY_DST) {
+            return Err(OdcError::TextureNotWritable(id));
+        }
+
+        if write.mip_level >= texture.info.mip_levels {
+            return Err(OdcError::MipLevelNotFound {
+                texture: id,
+                mip_level: write.mip_level,
+            });
+        }
+
+        let format = texture.info.format;
+        let (block_width, block_height) = format.describe().block_dimensions;
+        let (block_width, block_height) = (block_width as u32, block_height as u32);
+        if write.offset.x % block_width != 0
+            || write.offset.y % block_height != 0
+            || write.size.width % block_width != 0
+            || write.size.height % block_height != 0
+        {
+            return Err(OdcError::TextureWriteNotBlockAligned(id));
+        }
+
+        // Depth of 3D texture shrinks with mip level, while array layers count doesn't.
+        // Compressed mip levels are rounded up to whole blocks.
+        let is_3d = texture.info.dimension == wgpu::TextureDimension::D3;
+        let mip_size = texture
+            .info
+            .size
+            .mip_level_size(write.mip_level, is_3d)
+            .physical_size(format);
+        let fits = |offset: u32, size: u32, bound: u32| {
+            offset.checked_add(size).is_some_and(|end| end <= bound)
+        };
+        if !fits(write.offset.x, write.size.width, mip_size.width)
+            || !fits(write.offset.y, write.size.height, mip_size.height)
+            || !fits(
+                write.offset.z,
+                write.size.depth_or_array_layers,
+                mip_size.depth_or_array_layers,
+            )
+        {
+            return Err(OdcError::TextureWriteOutOfBounds(id));
+        }
+
+        let required = data
+            .required_size(format, write.size)
+            .ok_or(OdcError::TextureDataLayoutInvalid(id))?;
+        if (data.data.len() as u64) < required {
+            return Err(OdcError::TextureDataTooShort {
+                texture: id,
+                size: data.data.len(),
+                required,
+            });
+        }
+
         let texture_copy = wgpu::ImageCopyTexture {
-            texture,
+            texture: &texture.handle,
             aspect: wgpu::TextureAspect::All,
             mip_level: write.mip_level,
             origin: write.offset,
@@ -213,60 +389,200 @@ impl OdcCore {
         self.device
             .queue
             .write_texture(texture_copy, data.data, layout, write.size);
+
+        if write.mip_level == 0 && self.model.textures[id.0].auto_mipmaps {
+            self.generate_mipmaps_inner(id, texture)?;
+        }
+        Ok(())
     }
 
-    pub fn insert_stock_buffer(&mut self, typ: BufferType, name: String, size: Option<u64>) {
+    /// Fills mip levels of texture from level 0. Texture must be writable, 2D or cube and have
+    /// filterable and renderable format.
+    pub fn generate_mipmaps(&self, id: mdl::TextureId) {
+        self.try_generate_mipmaps(id).unwrap()
+    }
+
+    pub fn try_generate_mipmaps(&self, id: mdl::TextureId) -> OdcResult<()> {
+        let texture = self
+            .resources
+            .textures
+            .get(id.0)
+            .ok_or(OdcError::TextureNotFound(id))?;
+        self.generate_mipmaps_inner(id, texture)
+    }
+
+    pub fn generate_stock_mipmaps(&self, name: &str) {
+        self.try_generate_stock_mipmaps(name).unwrap()
+    }
+
+    pub fn try_generate_stock_mipmaps(&self, name: &str) -> OdcResult<()> {
+        let (id, texture) = self.resources.stock.texture(name)?;
+        self.generate_mipmaps_inner(*id, texture)
+    }
+
+    fn generate_mipmaps_inner(&self, id: mdl::TextureId, texture: &res::Texture) -> OdcResult<()> {
+        let required =
+            wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT;
+        if texture.info.mip_levels < 2 {
+            return Ok(());
+        }
+        if !texture.info.usages.contains(required) {
+            return Err(OdcError::MipmapsNotSupported(id));
+        }
+
+        let device = &self.device.device;
+        let mut encoder = device.create_command_encoder(&Default::default());
+        if !self.mipmaps.generate(device, &mut encoder, texture) {
+            return Err(OdcError::MipmapsNotSupported(id));
+        }
+        self.device.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+
+    pub fn insert_stock_buffer(&mut self, id: mdl::BufferId, name: String, size: Option<u64>) {
+        self.try_insert_stock_buffer(id, name, size).unwrap()
+    }
+
+    pub fn try_insert_stock_buffer(
+        &mut self,
+        id: mdl::BufferId,
+        name: String,
+        size: Option<u64>,
+    ) -> OdcResult<()> {
         self.resources
-            .insert_stock_buffer(&self.device.device, typ, name, size);
+            .insert_stock_buffer(&self.device.device, id, name, size)
     }
 
     pub fn swap_stock_buffer(&mut self, name: &str) {
-        self.resources.swap_stock_buffer(name);
-        if self.resources.stock.buffer(name).0 == BufferType::Uniform {
-            let factory = BindGroupFactory::new(&self.device.device, &self.resources);
-            for bind_group in self.model.uniform_bind_groups() {
-                let bind_group = &mut self.bind_groups.0[bind_group];
-                factory.refresh_bind_group(bind_group)
-            }
+        self.try_swap_stock_buffer(name).unwrap()
+    }
+
+    pub fn try_swap_stock_buffer(&mut self, name: &str) -> OdcResult<()> {
+        self.resources.swap_stock_buffer(name)?;
+        let id = self.resources.stock.buffer(name)?.0;
+        let factory = BindGroupFactory::new(&self.device.device, &self.resources);
+        for bind_group in self.model.buffer_bind_groups(id) {
+            let bind_group = &mut self.bind_groups.0[bind_group.0];
+            factory.refresh_bind_group(bind_group)
         }
+        Ok(())
     }
 
     pub fn remove_stock_buffer(&mut self, name: &str) {
-        self.resources.remove_stock_buffer(name);
+        self.try_remove_stock_buffer(name).unwrap()
+    }
+
+    pub fn try_remove_stock_buffer(&mut self, name: &str) -> OdcResult<()> {
+        self.resources.remove_stock_buffer(name)
     }
 
-    pub fn insert_stock_texture(&mut self, id: usize, name: String, size: Option<mdl::Extent3d>) {
+    pub fn insert_stock_texture(
+        &mut self,
+        id: mdl::TextureId,
+        name: String,
+        size: Option<mdl::Extent3d>,
+    ) {
+        self.try_insert_stock_texture(id, name, size).unwrap()
+    }
+
+    pub fn try_insert_stock_texture(
+        &mut self,
+        id: mdl::TextureId,
+        name: String,
+        size: Option<mdl::Extent3d>,
+    ) -> OdcResult<()> {
         if self.model.has_texture_attachment(id) && size.is_some() {
-            panic!("Can't create stock texture, which used as attachment")
+            return Err(OdcError::StockAttachmentResize(id));
         }
         self.resources
-            .insert_stock_texture(&self.device.device, id, name, size);
+            .insert_stock_texture(&self.device.device, id, name, size)
     }
 
     pub fn swap_stock_texture(&mut self, name: &str) {
-        self.resources.swap_stock_texture(name);
-        let id = self.resources.stock.texture(name).0;
+        self.try_swap_stock_texture(name).unwrap()
+    }
+
+    pub fn try_swap_stock_texture(&mut self, name: &str) -> OdcResult<()> {
+        self.resources.swap_stock_texture(name)?;
+        let id = self.resources.stock.texture(name)?.0;
         let factory = BindGroupFactory::new(&self.device.device, &self.resources);
-        for bind_group_index in self.model.texture_bind_groups(id) {
-            factory.refresh_bind_group(&mut self.bind_groups.0[bind_group_index]);
+        for bind_group_id in self.model.texture_bind_groups(id) {
+            factory.refresh_bind_group(&mut self.bind_groups.0[bind_group_id.0]);
         }
+        Ok(())
     }
 
     pub fn remove_stock_texture(&mut self, name: &str) {
-        self.resources.remove_stock_texture(name);
+        self.try_remove_stock_texture(name).unwrap()
+    }
+
+    pub fn try_remove_stock_texture(&mut self, name: &str) -> OdcResult<()> {
+        self.resources.remove_stock_texture(name)
+    }
+
+    pub fn draw<'a, StepIter, S>(&'a self, steps: StepIter)
+    where
+        StepIter: Iterator<Item = S>,
+        S: Into<Step<'a>>,
+    {
+        self.try_draw(steps).unwrap()
     }
 
-    pub fn draw<'a, RenderIter>(&'a self, steps: RenderIter)
+    /// Nothing is encoded or submitted, if any step is invalid.
+    pub fn try_draw<'a, StepIter, S>(&'a self, steps: StepIter) -> OdcResult<()>
     where
-        RenderIter: Iterator<Item = RenderStep<'a>>,
+        StepIter: Iterator<Item = S>,
+        S: Into<Step<'a>>,
     {
+        // Consecutive draws into the same pass are collected to be drawn in single render pass.
+        let mut batches = Vec::new();
+        let mut pass = None;
+        let mut draws = Vec::new();
+        for step in steps {
+            let step = step.into();
+            let step_pass = match &step {
+                Step::Render(step) => Some(step.pass),
+                Step::Pass(step) => Some(step.pass),
+                Step::Compute(_) => None,
+            };
+
+            if let Some(pass) = pass.filter(|&pass| step_pass != Some(pass)) {
+                let draws = mem::take(&mut draws);
+                batches.push(DrawBatch::Pass { pass, draws });
+            }
+            pass = step_pass;
+
+            match step {
+                Step::Render(step) => draws.push(step.into()),
+                Step::Pass(step) => draws.extend_from_slice(step.pipelines),
+                Step::Compute(step) => batches.push(DrawBatch::Compute(step)),
+            }
+        }
+
+        if let Some(pass) = pass {
+            batches.push(DrawBatch::Pass { pass, draws });
+        }
+
+        // Steps are checked before encoding, so invalid step leaves no partially encoded commands.
+        for batch in batches.iter() {
+            match batch {
+                DrawBatch::Pass { pass, draws } => self.check_pass(*pass, draws)?,
+                DrawBatch::Compute(step) => self.check_compute(step)?,
+            }
+        }
+
         let mut encoder = self
             .device
             .device
             .create_command_encoder(&Default::default());
-
-        for step in steps {
-            self.draw_pass(&mut encoder, step)
+        for batch in batches {
+            match batch {
+                DrawBatch::Pass { pass, draws } => {
+                    let pipelines = &draws;
+                    self.draw_pass(&mut encoder, PassStep { pass, pipelines });
+                }
+                DrawBatch::Compute(step) => self.dispatch(&mut encoder, step),
+            }
         }
 
         let window_frames: Vec<_> = self
@@ -279,40 +595,144 @@ impl OdcCore {
         for frame in window_frames {
             frame.present();
         }
+        Ok(())
     }
 
-    fn draw_pass(&self, encoder: &mut wgpu::CommandEncoder, step: RenderStep) {
+    fn check_pass(&self, pass: mdl::PassId, draws: &[PipelineDraws]) -> OdcResult<()> {
+        let pass_info = self
+            .model
+            .passes
+            .get(pass.0)
+            .ok_or(OdcError::PassNotFound(pass))?;
+        let size = self.pass_size(pass);
+        for draws in draws {
+            let pipeline = draws.pipeline;
+            self.pipelines
+                .render
+                .get(pipeline.0)
+                .ok_or(OdcError::PipelineNotFound(pipeline))?;
+            if !pass_info.pipelines.contains(&pipeline) {
+                return Err(OdcError::PipelineNotInPass { pipeline, pass });
+            }
+            if let Some(viewport) = draws.viewport {
+                viewport.check()?;
+            }
+            if let Some(scissor) = draws.scissor {
+                scissor.check(size)?;
+            }
+            for data in draws.data {
+                self.check_draw_data(pipeline, data)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_compute(&self, step: &ComputeStep) -> OdcResult<()> {
+        self.pipelines
+            .compute
+            .get(step.pipeline.0)
+            .ok_or(OdcError::ComputePipelineNotFound(step.pipeline))?;
+        Ok(())
+    }
+
+    fn draw_pass(&self, encoder: &mut wgpu::CommandEncoder, step: PassStep) {
+        let label = self.pass_label(step.pass);
+        encoder.push_debug_group(&label);
+
         let color_views = self.pass_targets(step.pass);
         let color_attachments = self.pass_color_attachments(step.pass, color_views.iter());
 
         let depth_view = self.pass_depth_view(step.pass);
         let depth_attachment = depth_view
             .as_ref()
-            .map(|view| self.pass_depth_attachment(view));
+            .and_then(|view| self.pass_depth_attachment(step.pass, view));
 
         let descriptor = wgpu::RenderPassDescriptor {
-            label: None,
+            label: Some(&label),
             color_attachments: &color_attachments,
             depth_stencil_attachment: depth_attachment,
         };
         let mut render_pass = encoder.begin_render_pass(&descriptor);
-        self.resources.buffers.bind_buffers(&mut render_pass);
 
-        self.draw_pipeline(&mut render_pass, step);
+        let size = self.pass_size(step.pass);
+        for draws in step.pipelines {
+            self.draw_pipeline(&mut render_pass, draws, size);
+        }
+        drop(render_pass);
+
+        encoder.pop_debug_group();
+    }
+
+    fn dispatch(&self, encoder: &mut wgpu::CommandEncoder, step: ComputeStep) {
+        let pipeline = &self.pipelines.compute[step.pipeline.0];
+        let label = match &pipeline.info.name {
+            Some(name) => name.clone(),
+            None => format!("compute pipeline {}", step.pipeline.0),
+        };
+        encoder.push_debug_group(&label);
+
+        let descriptor = wgpu::ComputePassDescriptor {
+            label: Some(&label),
+        };
+        let mut compute_pass = encoder.begin_compute_pass(&descriptor);
+        compute_pass.set_pipeline(&pipeline.handle);
+
+        for (i, bind_group) in pipeline.info.bind_groups.iter().enumerate() {
+            let bind_group = &self.bind_groups.0[*bind_group];
+            let offsets = vec![0; bind_group.info.dynamic_offsets_count()];
+            compute_pass.set_bind_group(i as _, &bind_group.handle, &offsets);
+        }
+
+        let [x, y, z] = step.workgroups;
+        compute_pass.dispatch(x, y, z);
+        drop(compute_pass);
+
+        encoder.pop_debug_group();
     }
 
-    fn pass_targets(&self, pass: usize) -> Vec<PassTargets> {
-        let pass_info = &self.model.passes[pass];
+    fn pass_label(&self, pass: mdl::PassId) -> String {
+        match &self.model.passes[pass.0].name {
+            Some(name) => name.clone(),
+            None => format!("pass {}", pass.0),
+        }
+    }
+
+    fn pass_size(&self, pass: mdl::PassId) -> mdl::Size2d {
+        let pass_info = &self.model.passes[pass.0];
+        let color_iter = pass_info
+            .color_attachments
+            .iter()
+            .map(|att| (att.texture, att.range));
+        let depth_iter = pass_info
+            .depth_attachment
+            .iter()
+            .map(|att| (att.texture, att.range));
+        color_iter
+            .chain(depth_iter)
+            .next()
+            .map(|(id, range)| {
+                let size = self.resources.textures[id.0].info.size;
+                let size = size.mip_level_size(range.base_mip_level, false);
+                mdl::Size2d {
+                    x: size.width,
+                    y: size.height,
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    fn pass_targets(&self, pass: mdl::PassId) -> Vec<PassTargets> {
+        let pass_info = &self.model.passes[pass.0];
 
         pass_info
             .color_attachments
             .iter()
             .map(|attachment| {
-                let texture = &self.resources.textures[attachment.texture];
-                let color = texture.create_view(None);
-                let resolve = attachment.resolve.map(|index| {
-                    let texture = &self.resources.textures[index];
-                    texture.create_view(None)
+                let texture = &self.resources.textures[attachment.texture.0];
+                let color = Self::attachment_view(texture, attachment.range);
+                let resolve = attachment.resolve.map(|id| {
+                    let texture = &self.resources.textures[id.0];
+                    Self::attachment_view(texture, Default::default())
                 });
                 PassTargets { color, resolve }
             })
@@ -321,13 +741,13 @@ impl OdcCore {
 
     fn pass_color_attachments<'a, ViewsIter>(
         &self,
-        pass: usize,
+        pass: mdl::PassId,
         views: ViewsIter,
     ) -> Vec<wgpu::RenderPassColorAttachment<'a>>
     where
         ViewsIter: Iterator<Item = &'a PassTargets>,
     {
-        let pass_info = &self.model.passes[pass];
+        let pass_info = &self.model.passes[pass.0];
 
         let attachments_iter = views.zip(pass_info.color_attachments.iter());
         attachments_iter
@@ -354,63 +774,322 @@ impl OdcCore {
             .collect()
     }
 
-    fn pass_depth_view(&self, pass: usize) -> Option<wgpu::TextureView> {
-        let pass_info = &self.model.passes[pass];
+    fn pass_depth_view(&self, pass: mdl::PassId) -> Option<wgpu::TextureView> {
+        let pass_info = &self.model.passes[pass.0];
         pass_info.depth_attachment.as_ref().map(|attachment| {
-            let texture = &self.resources.textures[attachment.texture];
-            texture.create_view(None)
+            let texture = &self.resources.textures[attachment.texture.0];
+            Self::attachment_view(texture, attachment.range)
         })
     }
 
+    fn attachment_view(texture: &res::Texture, range: mdl::ViewRange) -> wgpu::TextureView {
+        let range = ModelParser::parse_view_range(range);
+        texture.create_view(Some(wgpu::TextureViewDimension::D2), &range)
+    }
+
     fn pass_depth_attachment<'a>(
         &self,
+        pass: mdl::PassId,
         view: &'a wgpu::TextureView,
-    ) -> wgpu::RenderPassDepthStencilAttachment<'a> {
-        wgpu::RenderPassDepthStencilAttachment {
-            view,
-            depth_ops: Some(wgpu::Operations {
-                load: wgpu::LoadOp::Clear(1.0),
-                store: true,
-            }),
-            stencil_ops: None,
+    ) -> Option<wgpu::RenderPassDepthStencilAttachment<'a>> {
+        let pass_info = &self.model.passes[pass.0];
+        pass_info.depth_attachment.as_ref().map(|info| {
+            let texture = &self.resources.textures[info.texture.0];
+            let stencil_ops = if texture.info.has_stencil() {
+                Some(Self::attachment_ops(info.stencil))
+            } else {
+                None
+            };
+
+            wgpu::RenderPassDepthStencilAttachment {
+                view,
+                depth_ops: Some(Self::attachment_ops(info.depth)),
+                stencil_ops,
+            }
+        })
+    }
+
+    fn attachment_ops<T>(ops: mdl::AttachmentOps<T>) -> wgpu::Operations<T> {
+        let load = match ops.clear {
+            Some(value) => wgpu::LoadOp::Clear(value),
+            None => wgpu::LoadOp::Load,
+        };
+
+        wgpu::Operations {
+            load,
+            store: ops.store,
         }
     }
 
-    fn draw_pipeline<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, step: RenderStep) {
-        pass.set_pipeline(&self.pipelines.render[step.pipeline].handle);
-        let pipeline_info = &self.model.pipelines[step.pipeline];
+    fn draw_pipeline<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        step: &PipelineDraws,
+        size: mdl::Size2d,
+    ) {
+        let pipeline = &self.pipelines.render[step.pipeline.0];
+        if let Some(name) = &pipeline.info.name {
+            pass.insert_debug_marker(name);
+        }
+        pass.set_pipeline(&pipeline.handle);
+        pass.set_stencil_reference(step.stencil_reference);
+
+        // Render pass is shared by pipelines, so state of previous pipeline is always overwritten.
+        let viewport = step.viewport.unwrap_or_else(|| Viewport::full(size));
+        pass.set_viewport(
+            viewport.x,
+            viewport.y,
+            viewport.width,
+            viewport.height,
+            viewport.min_depth,
+            viewport.max_depth,
+        );
+        let scissor = step.scissor.unwrap_or_else(|| Scissor::full(size));
+        pass.set_scissor_rect(scissor.x, scissor.y, scissor.width, scissor.height);
+        let [r, g, b, a] = step.blend_constant.unwrap_or_default();
+        pass.set_blend_constant(wgpu::Color { r, g, b, a });
+
+        let buffers = &self.resources.buffers;
+        let input = &pipeline.info.input;
+        if let Some(index) = input.index {
+            let buffer = &buffers[index].handle;
+            pass.set_index_buffer(buffer.slice(..), input.index_format);
+        }
+        for (slot, stream) in input.streams.iter().enumerate() {
+            let buffer = &buffers[stream.buffer_index].handle;
+            pass.set_vertex_buffer(slot as _, buffer.slice(..));
+        }
 
-        for (i, bind_group) in pipeline_info.bind_groups.iter().enumerate() {
-            let bind_groups = &self.bind_groups.0;
-            pass.set_bind_group(i as _, &bind_groups[*bind_group].handle, &[]);
+        // Dynamic offsets are zero until set with `DrawData::BindGroup`.
+        for (i, bind_group) in pipeline.info.bind_groups.iter().enumerate() {
+            let bind_group = &self.bind_groups.0[*bind_group];
+            let offsets = vec![0; bind_group.info.dynamic_offsets_count()];
+            pass.set_bind_group(i as _, &bind_group.handle, &offsets);
         }
 
         for draw in step.data.iter() {
-            pass.draw_indexed(
-                draw.indices.clone(),
-                draw.base_vertex,
-                draw.instances.clone(),
-            );
+            self.draw_data(pass, draw);
         }
     }
 
-    fn create_resources(device: &wgpu::Device, parser: &ModelParser) -> Resources {
-        let factory = ResourceFactory::new(device);
+    fn check_draw_data(&self, pipeline: mdl::PipelineId, data: &DrawData) -> OdcResult<()> {
+        match data {
+            DrawData::BindGroup {
+                index,
+                bind_group,
+                offsets,
+            } => {
+                let bind_groups = &self.bind_groups.0;
+                let new = bind_groups
+                    .get(bind_group.0)
+                    .ok_or(OdcError::BindGroupNotFound(*bind_group))?;
+                let pipeline_bind_groups = &self.pipelines.render[pipeline.0].info.bind_groups;
+                let current = pipeline_bind_groups.get(*index as usize).ok_or(
+                    OdcError::BindGroupIndexOutOfRange {
+                        pipeline,
+                        index: *index,
+                    },
+                )?;
+                if !bind_groups[*current].info.is_compatible(&new.info) {
+                    return Err(OdcError::BindGroupIncompatible {
+                        pipeline,
+                        index: *index,
+                        bind_group: *bind_group,
+                    });
+                }
+                let buffers = &self.resources.buffers;
+                new.info.check_dynamic_offsets(
+                    *bind_group,
+                    offsets,
+                    &self.device.limits,
+                    |index| buffers[index].info.size,
+                )?;
+            }
+            DrawData::PushConstants {
+                stages,
+                offset,
+                data,
+            } => {
+                let stages = ModelParser::parse_visibility(*stages);
+                self.check_push_constants(pipeline, stages, *offset, data.len())?;
+            }
+            DrawData::Indexed { .. } => self.check_index_buffer(pipeline)?,
+            DrawData::Vertices { .. } => {}
+            DrawData::Indirect { buffer, offset } => {
+                let size = DrawData::INDIRECT_ARGS_SIZE;
+                self.check_indirect(*buffer, *offset, 1, size)?;
+            }
+            DrawData::IndexedIndirect { buffer, offset } => {
+                self.check_index_buffer(pipeline)?;
+                let size = DrawData::INDEXED_INDIRECT_ARGS_SIZE;
+                self.check_indirect(*buffer, *offset, 1, size)?;
+            }
+            DrawData::MultiIndirect {
+                buffer,
+                offset,
+                count,
+            } => {
+                let size = DrawData::INDIRECT_ARGS_SIZE;
+                self.check_indirect(*buffer, *offset, *count, size)?;
+            }
+            DrawData::MultiIndexedIndirect {
+                buffer,
+                offset,
+                count,
+            } => {
+                self.check_index_buffer(pipeline)?;
+                let size = DrawData::INDEXED_INDIRECT_ARGS_SIZE;
+                self.check_indirect(*buffer, *offset, *count, size)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_index_buffer(&self, pipeline: mdl::PipelineId) -> OdcResult<()> {
+        let input = &self.pipelines.render[pipeline.0].info.input;
+        if input.index.is_none() {
+            return Err(OdcError::PipelineWithoutIndexBuffer(pipeline));
+        }
+        Ok(())
+    }
+
+    fn check_indirect(
+        &self,
+        buffer: mdl::BufferId,
+        offset: u64,
+        count: u32,
+        args_size: u64,
+    ) -> OdcResult<()> {
+        let info = &self
+            .resources
+            .buffers
+            .get(buffer.0)
+            .ok_or(OdcError::BufferNotFound(buffer))?
+            .info;
+        info.check_indirect(buffer, offset, count, args_size)
+    }
 
-        let index_info = parser.index_info();
-        let index = factory.create_buffer(index_info);
+    /// `data` must be checked with `check_draw_data`.
+    fn draw_data<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>, data: &DrawData) {
+        let buffers = &self.resources.buffers;
+        let multi_draw = self
+            .device
+            .features
+            .contains(wgpu::Features::MULTI_DRAW_INDIRECT);
+
+        match data {
+            DrawData::Indexed {
+                indices,
+                base_vertex,
+                instances,
+            } => pass.draw_indexed(indices.clone(), *base_vertex, instances.clone()),
+            DrawData::Vertices {
+                vertices,
+                instances,
+            } => pass.draw(vertices.clone(), instances.clone()),
+            DrawData::BindGroup {
+                index,
+                bind_group,
+                offsets,
+            } => {
+                let bind_group = &self.bind_groups.0[bind_group.0];
+                pass.set_bind_group(*index, &bind_group.handle, offsets);
+            }
+            DrawData::PushConstants {
+                stages,
+                offset,
+                data,
+            } => {
+                let stages = ModelParser::parse_visibility(*stages);
+                pass.set_push_constants(stages, *offset, data);
+            }
+            DrawData::Indirect { buffer, offset } => {
+                pass.draw_indirect(&buffers[buffer.0].handle, *offset)
+            }
+            DrawData::IndexedIndirect { buffer, offset } => {
+                pass.draw_indexed_indirect(&buffers[buffer.0].handle, *offset)
+            }
+            DrawData::MultiIndirect {
+                buffer,
+                offset,
+                count,
+            } => {
+                let buffer = &buffers[buffer.0].handle;
+                if multi_draw {
+                    pass.multi_draw_indirect(buffer, *offset, *count);
+                } else {
+                    for i in 0..*count as u64 {
+                        pass.draw_indirect(buffer, offset + i * DrawData::INDIRECT_ARGS_SIZE);
+                    }
+                }
+            }
+            DrawData::MultiIndexedIndirect {
+                buffer,
+                offset,
+                count,
+            } => {
+                let buffer = &buffers[buffer.0].handle;
+                if multi_draw {
+                    pass.multi_draw_indexed_indirect(buffer, *offset, *count);
+                } else {
+                    for i in 0..*count as u64 {
+                        let offset = offset + i * DrawData::INDEXED_INDIRECT_ARGS_SIZE;
+                        pass.draw_indexed_indirect(buffer, offset);
+                    }
+                }
+            }
+        }
+    }
 
-        let vertex_info = parser.vertex_info();
-        let vertex = factory.create_buffer(vertex_info);
+    /// Mirrors wgpu rules: data of `stages` must fit into ranges of those stages entirely.
+    fn check_push_constants(
+        &self,
+        pipeline: mdl::PipelineId,
+        stages: wgpu::ShaderStages,
+        offset: u32,
+        size: usize,
+    ) -> OdcResult<()> {
+        let alignment = wgpu::PUSH_CONSTANT_ALIGNMENT as usize;
+        if offset as usize % alignment != 0 || size % alignment != 0 {
+            return Err(OdcError::UnalignedPushConstants { offset, size });
+        }
 
-        let instance_info = parser.instance_info();
-        let instance = factory.create_buffer(instance_info);
+        let ranges = &self.pipelines.render[pipeline.0].info.push_constant_ranges;
+        let end = u32::try_from(size)
+            .ok()
+            .and_then(|size| offset.checked_add(size));
+        let fits = end.is_some_and(|end| {
+            let mut used_stages = wgpu::ShaderStages::NONE;
+            for range in ranges.iter() {
+                if stages.contains(range.stages) {
+                    if !(range.range.start <= offset && end <= range.range.end) {
+                        return false;
+                    }
+                    used_stages |= range.stages;
+                } else if stages.intersects(range.stages) {
+                    return false;
+                }
+            }
+            used_stages == stages
+        });
 
-        let uniform_info = parser.uniform_info();
-        let uniform = factory.create_buffer(uniform_info);
+        if !fits {
+            return Err(OdcError::PushConstantsOutOfRange {
+                pipeline,
+                offset,
+                size,
+            });
+        }
+        Ok(())
+    }
 
-        let buffers = Buffers::new(index, vertex, instance, uniform);
+    fn create_resources(device: &wgpu::Device, parser: &ModelParser) -> Resources {
+        let factory = ResourceFactory::new(device);
 
+        let buffers = parser
+            .buffers_info()
+            .map(|info| factory.create_buffer(info))
+            .collect();
         let textures = parser
             .textures_info()
             .map(|info| factory.create_texture(info))
@@ -460,33 +1139,251 @@ impl OdcCore {
         device: &wgpu::Device,
         parser: &ModelParser,
         bind_groups: &BindGroups,
-    ) -> Pipelines {
+    ) -> OdcResult<Pipelines> {
         let factory = PipelinesFactory::new(device, bind_groups);
 
         let render = parser
             .render_pipelines_info()
-            .map(|info| factory.create_render_pipeline(info))
-            .collect();
+            .map(|info| info.map(|info| factory.create_render_pipeline(info)))
+            .collect::<OdcResult<_>>()?;
+
+        let compute = parser
+            .compute_pipelines_info()
+            .map(|info| info.map(|info| factory.create_compute_pipeline(info)))
+            .collect::<OdcResult<_>>()?;
 
-        Pipelines { render }
+        Ok(Pipelines { render, compute })
     }
 }
 
-#[repr(C)]
+/// Single command of `RenderStep`. Indirect arguments are read from buffers with `indirect`
+/// usage and have layout of `wgpu::util::DrawIndirect` or `wgpu::util::DrawIndexedIndirect`.
 #[derive(Debug, Clone)]
-pub struct DrawData {
-    pub indices: Range<u32>,
-    pub base_vertex: i32,
-    pub instances: Range<u32>,
+pub enum DrawData {
+    /// Requires pipeline with index buffer.
+    Indexed {
+        indices: Range<u32>,
+        base_vertex: i32,
+        instances: Range<u32>,
+    },
+    Vertices {
+        vertices: Range<u32>,
+        instances: Range<u32>,
+    },
+    /// Replaces bind group at `index` for following draws of step, e.g. to switch material.
+    /// `bind_group` must have the same binding layouts as pipeline's bind group at `index`,
+    /// otherwise `OdcCore::try_draw` returns error.
+    BindGroup {
+        index: u32,
+        bind_group: mdl::BindGroupId,
+        /// Offsets of dynamic offset bindings in order of binding indices. Must be multiples of
+        /// `min_uniform_buffer_offset_alignment` or `min_storage_buffer_offset_alignment` limit
+        /// and keep bindings within their buffers.
+        offsets: Vec<u32>,
+    },
+    /// Sets push constants for following draws of step. `offset` and size of `data` must be
+    /// multiples of 4, written bytes must lie inside push constant range of each of `stages`.
+    PushConstants {
+        stages: mdl::ShaderStages,
+        offset: u32,
+        data: Vec<u8>,
+    },
+    Indirect {
+        buffer: mdl::BufferId,
+        offset: u64,
+    },
+    /// Requires pipeline with index buffer.
+    IndexedIndirect {
+        buffer: mdl::BufferId,
+        offset: u64,
+    },
+    /// `count` tightly packed indirect draws.
+    /// Issued one by one, if `MULTI_DRAW_INDIRECT` feature is not supported.
+    MultiIndirect {
+        buffer: mdl::BufferId,
+        offset: u64,
+        count: u32,
+    },
+    /// `count` tightly packed indexed indirect draws.
+    /// Issued one by one, if `MULTI_DRAW_INDIRECT` feature is not supported.
+    MultiIndexedIndirect {
+        buffer: mdl::BufferId,
+        offset: u64,
+        count: u32,
+    },
 }
 
+impl DrawData {
+    pub const INDIRECT_ARGS_SIZE: u64 = 16;
+    pub const INDEXED_INDIRECT_ARGS_SIZE: u64 = 20;
+}
+
+/// Draws of single pipeline. Consecutive steps with the same pass share one render pass, so
+/// attachments are cleared only once.
 #[derive(Debug, Copy, Clone)]
 pub struct RenderStep<'a> {
-    pub pass: usize,
-    pub pipeline: usize,
+    pub pass: mdl::PassId,
+    pub pipeline: mdl::PipelineId,
     pub data: &'a [DrawData],
+    /// Reference value for stencil test of pipeline.
+    pub stencil_reference: u32,
+    /// Whole pass target, if `None`.
+    pub viewport: Option<Viewport>,
+    /// Whole pass target, if `None`.
+    pub scissor: Option<Scissor>,
+    /// Color for `Constant` blend factors. Transparent black, if `None`.
+    pub blend_constant: Option<[f64; 4]>,
 }
 
+/// Draws of several pipelines in one render pass.
+#[derive(Debug, Copy, Clone)]
+pub struct PassStep<'a> {
+    pub pass: mdl::PassId,
+    pub pipelines: &'a [PipelineDraws<'a>],
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PipelineDraws<'a> {
+    pub pipeline: mdl::PipelineId,
+    pub data: &'a [DrawData],
+    /// Reference value for stencil test of pipeline.
+    pub stencil_reference: u32,
+    /// Whole pass target, if `None`.
+    pub viewport: Option<Viewport>,
+    /// Whole pass target, if `None`.
+    pub scissor: Option<Scissor>,
+    /// Color for `Constant` blend factors. Transparent black, if `None`.
+    pub blend_constant: Option<[f64; 4]>,
+}
+
+impl<'a> From<RenderStep<'a>> for PipelineDraws<'a> {
+    fn from(step: RenderStep<'a>) -> Self {
+        Self {
+            pipeline: step.pipeline,
+            data: step.data,
+            stencil_reference: step.stencil_reference,
+            viewport: step.viewport,
+            scissor: step.scissor,
+            blend_constant: step.blend_constant,
+        }
+    }
+}
+
+/// Area of pass target, which is mapped to clip space, and its depth range.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub min_depth: f32,
+    pub max_depth: f32,
+}
+
+impl Viewport {
+    pub fn full(size: mdl::Size2d) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: size.x as _,
+            height: size.y as _,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        }
+    }
+
+    /// Mirrors wgpu rules: size is positive, depth range is ordered and within `0..=1`.
+    fn check(&self) -> OdcResult<()> {
+        if !(self.width > 0.0 && self.height > 0.0) {
+            return Err(OdcError::ViewportEmpty {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        let depth = 0.0..=1.0;
+        if !(depth.contains(&self.min_depth)
+            && depth.contains(&self.max_depth)
+            && self.min_depth <= self.max_depth)
+        {
+            return Err(OdcError::ViewportDepthOutOfRange {
+                min_depth: self.min_depth,
+                max_depth: self.max_depth,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Area of pass target, outside of which fragments are discarded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Scissor {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Scissor {
+    pub fn full(size: mdl::Size2d) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: size.x,
+            height: size.y,
+        }
+    }
+
+    /// Scissor must be non-empty and fit into pass target of `size`.
+    fn check(&self, size: mdl::Size2d) -> OdcResult<()> {
+        if self.width == 0 || self.height == 0 {
+            return Err(OdcError::ScissorEmpty(*self));
+        }
+        let right = self.x as u64 + self.width as u64;
+        let bottom = self.y as u64 + self.height as u64;
+        if right > size.x as u64 || bottom > size.y as u64 {
+            return Err(OdcError::ScissorOutOfBounds {
+                scissor: *self,
+                size,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ComputeStep {
+    pub pipeline: mdl::ComputePipelineId,
+    pub workgroups: [u32; 3],
+}
+
+/// Single step of `OdcCore::draw`. Render and compute steps are executed in given order.
+#[derive(Debug, Copy, Clone)]
+pub enum Step<'a> {
+    Render(RenderStep<'a>),
+    Pass(PassStep<'a>),
+    Compute(ComputeStep),
+}
+
+impl<'a> From<PassStep<'a>> for Step<'a> {
+    fn from(step: PassStep<'a>) -> Self {
+        Self::Pass(step)
+    }
+}
+
+impl<'a> From<RenderStep<'a>> for Step<'a> {
+    fn from(step: RenderStep<'a>) -> Self {
+        Self::Render(step)
+    }
+}
+
+impl<'a> From<ComputeStep> for Step<'a> {
+    fn from(step: ComputeStep) -> Self {
+        Self::Compute(step)
+    }
+}
+
+/// Region of compressed texture must be aligned to blocks. Mip level size is rounded up to whole
+/// blocks, so region may cover blocks of mip level edge.
 #[derive(Debug, Copy, Clone)]
 pub struct TextureWrite {
     pub mip_level: u32,
@@ -497,11 +1394,205 @@ pub struct TextureWrite {
 #[derive(Debug, Copy, Clone)]
 pub struct TextureData<'a> {
     pub data: &'a [u8],
+    /// Size of row of blocks for compressed textures.
     pub bytes_per_row: u32,
+    /// Number of block rows for compressed textures.
     pub rows_per_layer: u32,
 }
 
+impl<'a> TextureData<'a> {
+    /// Tightly packed data of texture region with `size` in texels.
+    pub fn packed(data: &'a [u8], typ: mdl::TextureType, size: mdl::Extent3d) -> Self {
+        let format = ModelParser::parse_texture_format(typ).describe();
+        let (block_width, block_height) = format.block_dimensions;
+        let blocks_per_row = (size.width + block_width as u32 - 1) / block_width as u32;
+        let rows_per_layer = (size.height + block_height as u32 - 1) / block_height as u32;
+        Self {
+            data,
+            bytes_per_row: blocks_per_row * format.block_size as u32,
+            rows_per_layer,
+        }
+    }
+
+    /// Bytes of data, required to write region of `size` texels in `format`.
+    /// `None`, if rows or layers of region overlap in data.
+    fn required_size(&self, format: wgpu::TextureFormat, size: mdl::Extent3d) -> Option<u64> {
+        let format = format.describe();
+        let (block_width, block_height) = format.block_dimensions;
+        let row_size = (size.width / block_width as u32) as u64 * format.block_size as u64;
+        let rows = (size.height / block_height as u32) as u64;
+        let layers = size.depth_or_array_layers as u64;
+        if row_size == 0 || rows == 0 || layers == 0 {
+            return Some(0);
+        }
+
+        let bytes_per_row = self.bytes_per_row as u64;
+        let rows_per_layer = self.rows_per_layer as u64;
+        if (rows > 1 || layers > 1) && bytes_per_row < row_size {
+            return None;
+        }
+        if layers > 1 && rows_per_layer < rows {
+            return None;
+        }
+
+        let layer_size = bytes_per_row.checked_mul(rows_per_layer)?;
+        let last_layer = bytes_per_row.checked_mul(rows - 1)?.checked_add(row_size)?;
+        layer_size.checked_mul(layers - 1)?.checked_add(last_layer)
+    }
+}
+
+/// Steps of `OdcCore::try_draw`, grouped into render and compute passes.
+enum DrawBatch<'a> {
+    Pass {
+        pass: mdl::PassId,
+        draws: Vec<PipelineDraws<'a>>,
+    },
+    Compute(ComputeStep),
+}
+
 struct PassTargets {
     pub color: wgpu::TextureView,
     pub resolve: Option<wgpu::TextureView>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{OdcError, Scissor, TextureData, Viewport};
+    use crate::mdl::{Extent3d, Size2d};
+    use wgpu::TextureFormat;
+
+    fn extent(width: u32, height: u32, depth_or_array_layers: u32) -> Extent3d {
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers,
+        }
+    }
+
+    #[test]
+    fn texture_data_size() {
+        let format = TextureFormat::Rgba8Unorm;
+        let size = extent(4, 4, 2);
+        let data = TextureData {
+            data: &[],
+            bytes_per_row: 16,
+            rows_per_layer: 4,
+        };
+        assert_eq!(data.required_size(format, size), Some(128));
+
+        let padded = TextureData {
+            bytes_per_row: 32,
+            ..data
+        };
+        assert_eq!(
+            padded.required_size(format, size),
+            Some(32 * 4 + 32 * 3 + 16)
+        );
+
+        let compressed = TextureData {
+            bytes_per_row: 8,
+            rows_per_layer: 1,
+            ..data
+        };
+        let size = extent(4, 4, 1);
+        assert_eq!(
+            compressed.required_size(TextureFormat::Bc1RgbaUnorm, size),
+            Some(8)
+        );
+    }
+
+    #[test]
+    fn texture_data_overlap() {
+        let format = TextureFormat::Rgba8Unorm;
+        let data = TextureData {
+            data: &[],
+            bytes_per_row: 8,
+            rows_per_layer: 4,
+        };
+        assert_eq!(data.required_size(format, extent(4, 4, 1)), None);
+
+        let data = TextureData {
+            bytes_per_row: 16,
+            rows_per_layer: 2,
+            ..data
+        };
+        assert_eq!(data.required_size(format, extent(4, 4, 2)), None);
+        assert_eq!(data.required_size(format, extent(4, 1, 1)), Some(16));
+    }
+
+    #[test]
+    fn viewport_empty() {
+        let viewport = Viewport {
+            width: 0.0,
+            ..Viewport::full(Size2d { x: 64, y: 64 })
+        };
+        let result = viewport.check();
+        assert!(matches!(result, Err(OdcError::ViewportEmpty { .. })));
+
+        let viewport = Viewport {
+            height: -1.0,
+            ..viewport
+        };
+        let result = viewport.check();
+        assert!(matches!(result, Err(OdcError::ViewportEmpty { .. })));
+    }
+
+    #[test]
+    fn viewport_depth_out_of_range() {
+        let full = Viewport::full(Size2d { x: 64, y: 64 });
+        assert!(full.check().is_ok());
+
+        let viewport = Viewport {
+            max_depth: 1.5,
+            ..full
+        };
+        let result = viewport.check();
+        assert!(matches!(
+            result,
+            Err(OdcError::ViewportDepthOutOfRange { .. })
+        ));
+
+        let viewport = Viewport {
+            min_depth: -0.5,
+            ..full
+        };
+        let result = viewport.check();
+        assert!(matches!(
+            result,
+            Err(OdcError::ViewportDepthOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn scissor_empty() {
+        let size = Size2d { x: 64, y: 64 };
+        let scissor = Scissor {
+            height: 0,
+            ..Scissor::full(size)
+        };
+        let result = scissor.check(size);
+        assert!(matches!(result, Err(OdcError::ScissorEmpty(_))));
+    }
+
+    #[test]
+    fn scissor_out_of_bounds() {
+        let size = Size2d { x: 64, y: 64 };
+        assert!(Scissor::full(size).check(size).is_ok());
+
+        let scissor = Scissor {
+            x: 32,
+            ..Scissor::full(size)
+        };
+        let result = scissor.check(size);
+        assert!(matches!(result, Err(OdcError::ScissorOutOfBounds { .. })));
+
+        let scissor = Scissor {
+            x: u32::MAX,
+            y: 0,
+            width: 1,
+            height: 1,
+        };
+        let result = scissor.check(size);
+        assert!(matches!(result, Err(OdcError::ScissorOutOfBounds { .. })));
+    }
+}
@@ -1,42 +1,171 @@
+pub use crate::mdl_builder::RenderModelBuilder;
+use crate::mdl_validate::ModelValidator;
+pub use crate::mdl_validate::ValidationError;
 use std::collections::HashSet;
+use std::ops::Range;
 use std::path::PathBuf;
 pub use wgpu::{
-    BlendComponent, BlendFactor, BlendOperation, BlendState, Extent3d, Origin3d,
-    TextureViewDimension,
+    BlendComponent, BlendFactor, BlendOperation, BlendState, ColorWrites, CompareFunction,
+    DepthBiasState, Extent3d, Origin3d, StencilFaceState, StencilOperation, StencilState,
+    TextureAspect, TextureViewDimension,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RenderModel {
     pub passes: Vec<Pass>,
     pub pipelines: Vec<RenderPipeline>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compute_pipelines: Vec<ComputePipeline>,
     pub bind_groups: Vec<BindGroup>,
     pub textures: Vec<Texture>,
     pub samplers: Vec<Sampler>,
-    pub buffers: Buffers,
+    pub buffers: Vec<Buffer>,
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct Buffers {
-    pub index: u64,
-    pub vertex: u64,
-    pub instance: u64,
-    pub uniform: u64,
+/// Index of texture in `RenderModel`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct TextureId(pub usize);
+
+/// Index of sampler in `RenderModel`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SamplerId(pub usize);
+
+/// Index of buffer in `RenderModel`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct BufferId(pub usize);
+
+/// Index of bind group in `RenderModel`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct BindGroupId(pub usize);
+
+/// Index of render pipeline in `RenderModel`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct PipelineId(pub usize);
+
+/// Index of compute pipeline in `RenderModel`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ComputePipelineId(pub usize);
+
+/// Index of pass in `RenderModel`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct PassId(pub usize);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Buffer {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+    pub size: u64,
+    pub usage: BufferUsage,
+}
+
+/// Ways buffer can be used by pipelines. Any buffer can be written with `OdcCore::write_buffer`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BufferUsage {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub index: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vertex: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub uniform: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub storage: bool,
+    /// Source of indirect draw arguments.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub indirect: bool,
 }
 
 impl RenderModel {
-    pub fn has_texture_attachment(&self, index: usize) -> bool {
+    /// Checks model consistency. Returns all found problems. Empty result means valid model.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        ModelValidator::new(self).validate()
+    }
+
+    pub fn texture_by_name(&self, name: &str) -> Option<TextureId> {
+        find_by_name(&self.textures, name, |texture| &texture.name).map(TextureId)
+    }
+
+    pub fn sampler_by_name(&self, name: &str) -> Option<SamplerId> {
+        find_by_name(&self.samplers, name, |sampler| &sampler.name).map(SamplerId)
+    }
+
+    pub fn buffer_by_name(&self, name: &str) -> Option<BufferId> {
+        find_by_name(&self.buffers, name, |buffer| &buffer.name).map(BufferId)
+    }
+
+    pub fn bind_group_by_name(&self, name: &str) -> Option<BindGroupId> {
+        find_by_name(&self.bind_groups, name, |bind_group| &bind_group.name).map(BindGroupId)
+    }
+
+    pub fn pipeline_by_name(&self, name: &str) -> Option<PipelineId> {
+        find_by_name(&self.pipelines, name, |pipeline| &pipeline.name).map(PipelineId)
+    }
+
+    pub fn compute_pipeline_by_name(&self, name: &str) -> Option<ComputePipelineId> {
+        find_by_name(&self.compute_pipelines, name, |pipeline| &pipeline.name)
+            .map(ComputePipelineId)
+    }
+
+    pub fn pass_by_name(&self, name: &str) -> Option<PassId> {
+        find_by_name(&self.passes, name, |pass| &pass.name).map(PassId)
+    }
+
+    pub fn has_texture_attachment(&self, index: TextureId) -> bool {
         self.passes
             .iter()
             .any(|pass| pass.has_texture_attachment(index))
     }
 
-    pub fn has_texture_binding(&self, index: usize) -> bool {
+    pub fn has_texture_binding(&self, index: TextureId) -> bool {
         self.bind_groups
             .iter()
             .any(|bind_group| bind_group.has_texture(index))
     }
 
-    pub fn connected_attachments(&self, index: usize) -> impl Iterator<Item = usize> {
+    pub fn has_storage_texture_binding(&self, index: TextureId) -> bool {
+        self.bind_groups
+            .iter()
+            .any(|bind_group| bind_group.has_storage_texture(index))
+    }
+
+    pub fn connected_attachments(&self, index: TextureId) -> impl Iterator<Item = TextureId> {
         let mut connected = HashSet::with_capacity(16);
         connected.insert(index);
         let mut prev_len = 0;
@@ -61,36 +190,71 @@ impl RenderModel {
         connected.into_iter()
     }
 
-    pub fn texture_bind_groups(&self, texture: usize) -> HashSet<usize> {
-        self.bind_groups.iter().enumerate().filter_map(|(i, bind_group)| {
-            if bind_group.has_texture(texture) {
-                Some(i)
-            } else {
-                None
-            }
-        }).collect()
+    pub fn texture_bind_groups(&self, texture: TextureId) -> HashSet<BindGroupId> {
+        self.bind_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(i, bind_group)| {
+                if bind_group.has_texture(texture) || bind_group.has_storage_texture(texture) {
+                    Some(BindGroupId(i))
+                } else {
+                    None
+                }
+            })
+            .collect()
     }
 
-    pub fn uniform_bind_groups(&self) -> HashSet<usize> {
-        self.bind_groups.iter().enumerate().filter_map(|(i, bind_group)| {
-            if bind_group.has_uniform() {
-                Some(i)
-            } else {
-                None
-            }
-        }).collect()
+    pub fn buffer_bind_groups(&self, buffer: BufferId) -> HashSet<BindGroupId> {
+        self.bind_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(i, bind_group)| {
+                if bind_group.has_buffer(buffer) {
+                    Some(BindGroupId(i))
+                } else {
+                    None
+                }
+            })
+            .collect()
     }
 }
 
-#[derive(Debug, Clone)]
+impl RenderModel {
+    /// Sample count of the first pass attachment.
+    pub fn pass_sample_count(&self, pass: PassId) -> Option<SampleCount> {
+        let pass = &self.passes[pass.0];
+        let color_iter = pass.color_attachments.iter().map(|att| att.texture);
+        let depth_iter = pass.depth_attachment.iter().map(|att| att.texture);
+        color_iter
+            .chain(depth_iter)
+            .find_map(|texture| self.textures.get(texture.0))
+            .map(|texture| texture.sample_count)
+    }
+}
+
+/// Returns index of first item with `name`.
+fn find_by_name<T>(
+    items: &[T],
+    name: &str,
+    item_name: impl Fn(&T) -> &Option<String>,
+) -> Option<usize> {
+    items
+        .iter()
+        .position(|item| item_name(item).as_deref() == Some(name))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pass {
-    pub pipelines: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+    pub pipelines: Vec<PipelineId>,
     pub color_attachments: Vec<Attachment>,
     pub depth_attachment: Option<DepthAttachment>,
 }
 
 impl Pass {
-    pub fn has_texture_attachment(&self, index: usize) -> bool {
+    pub fn has_texture_attachment(&self, index: TextureId) -> bool {
         let color_attachment = self
             .color_attachments
             .iter()
@@ -104,33 +268,305 @@ impl Pass {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attachment {
-    pub texture: usize,
-    pub resolve: Option<usize>,
+    pub texture: TextureId,
+    pub resolve: Option<TextureId>,
     pub clear: Option<[f64; 4]>,
     pub store: bool,
+    /// Must select single mip level and array layer. Applied to `texture` only.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub range: ViewRange,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DepthAttachment {
-    pub texture: usize,
+    pub texture: TextureId,
+    /// Must select single mip level and array layer of all aspects.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub range: ViewRange,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub depth: AttachmentOps<f32>,
+    /// Ignored, if attachment format has no stencil aspect.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stencil: AttachmentOps<u32>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+/// Load and store operations for depth or stencil aspect of attachment.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttachmentOps<T> {
+    /// Value to clear attachment with. Previous content is loaded, if `None`.
+    pub clear: Option<T>,
+    pub store: bool,
+}
+
+impl Default for AttachmentOps<f32> {
+    fn default() -> Self {
+        Self {
+            clear: Some(1.0),
+            store: true,
+        }
+    }
+}
+
+impl Default for AttachmentOps<u32> {
+    fn default() -> Self {
+        Self {
+            clear: Some(0),
+            store: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RenderPipeline {
-    pub input: Option<PipelineInpit>,
-    pub bind_groups: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+    pub input: PipelineInpit,
+    pub bind_groups: Vec<BindGroupId>,
+    /// Requires `PUSH_CONSTANTS` device feature, if not empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub push_constants: Vec<PushConstantRange>,
     pub shader: Shader,
-    pub blend: Vec<Option<BlendState>>,
+    /// Outputs to color attachments of pass in order of declaration.
+    pub targets: Vec<ColorTarget>,
+    /// Required, if and only if passes of pipeline have depth attachment.
     pub depth: Option<DepthOps>,
-    pub multisampled: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub multisample: Multisample,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub primitive: Primitive,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Default)]
-pub struct DepthOps {}
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ColorTarget {
+    pub blend: Option<BlendState>,
+    pub write_mask: ColorWrites,
+}
+
+impl From<Blend> for ColorTarget {
+    fn from(blend: Blend) -> Self {
+        Self {
+            blend: Some(blend.into()),
+            write_mask: ColorWrites::ALL,
+        }
+    }
+}
+
+/// Common blend states.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Blend {
+    /// Source over destination, weighted by source alpha.
+    Alpha,
+    /// Source over destination for colors, premultiplied by alpha.
+    Premultiplied,
+    /// Sum of source and destination.
+    Additive,
+    /// Product of source and destination.
+    Multiply,
+    /// Source overwrites destination.
+    Replace,
+}
+
+impl From<Blend> for BlendState {
+    fn from(blend: Blend) -> Self {
+        match blend {
+            Blend::Alpha => BlendState::ALPHA_BLENDING,
+            Blend::Premultiplied => BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            Blend::Additive => {
+                let component = BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                };
+                BlendState {
+                    color: component,
+                    alpha: component,
+                }
+            }
+            Blend::Multiply => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::Dst,
+                    dst_factor: BlendFactor::Zero,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::DstAlpha,
+                    dst_factor: BlendFactor::Zero,
+                    operation: BlendOperation::Add,
+                },
+            },
+            Blend::Replace => BlendState::REPLACE,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Multisample {
+    /// Must match sample count of pass attachments.
+    pub count: SampleCount,
+    /// Use fragment alpha as coverage mask. Requires multisampling.
+    pub alpha_to_coverage: bool,
+    /// Mask of samples, which are written.
+    pub mask: u64,
+}
+
+impl Default for Multisample {
+    fn default() -> Self {
+        Self {
+            count: SampleCount::One,
+            alpha_to_coverage: false,
+            mask: !0,
+        }
+    }
+}
+
+/// Number of samples per texel. Only 2D textures can be multisampled.
+/// wgpu doesn't report sample counts, supported by adapter, so only `One` and `Four` are portable.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SampleCount {
+    #[default]
+    One,
+    Two,
+    Four,
+    Eight,
+}
+
+impl SampleCount {
+    pub fn count(&self) -> u32 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+            Self::Four => 4,
+            Self::Eight => 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct DepthOps {
+    pub compare: CompareFunction,
+    /// Write depth of fragments, which passed the test.
+    pub write: bool,
+    /// Depth bias, e.g. to avoid shadow acne.
+    pub bias: DepthBiasState,
+    /// Stencil test. Requires depth attachment format with stencil aspect.
+    pub stencil: StencilState,
+}
+
+impl Default for DepthOps {
+    fn default() -> Self {
+        Self {
+            compare: CompareFunction::Less,
+            write: true,
+            bias: DepthBiasState::default(),
+            stencil: StencilState::default(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Primitive {
+    pub topology: Topology,
+    /// Format of indices, which restart strip. Allowed only for strip topologies and must match
+    /// index format of pipeline input.
+    pub strip_index_format: Option<IndexFormat>,
+    pub front_face: FrontFace,
+    pub cull_mode: CullMode,
+    /// `Line` and `Point` modes require corresponding device features.
+    pub polygon_mode: PolygonMode,
+    /// Disables depth clipping. Requires `DEPTH_CLIP_CONTROL` device feature.
+    pub unclipped_depth: bool,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    PointList,
+    LineList,
+    LineStrip,
+    #[default]
+    TriangleList,
+    TriangleStrip,
+}
+
+impl Topology {
+    pub fn is_strip(&self) -> bool {
+        matches!(self, Self::LineStrip | Self::TriangleStrip)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexFormat {
+    Uint16,
+    #[default]
+    Uint32,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrontFace {
+    #[default]
+    Ccw,
+    Cw,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CullMode {
+    #[default]
+    None,
+    Front,
+    Back,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolygonMode {
+    #[default]
+    Fill,
+    Line,
+    Point,
+}
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComputePipeline {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+    pub bind_groups: Vec<BindGroupId>,
+    pub shader: ComputeShader,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Shader {
     pub path: PathBuf,
     pub vs_main: String,
@@ -138,18 +574,43 @@ pub struct Shader {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComputeShader {
+    pub path: PathBuf,
+    pub cs_main: String,
+}
+
+/// Buffers bound while pipeline is drawing.
+/// Vertex streams are bound to slots in order of declaration.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PipelineInpit {
-    pub vertex: InputInfo,
-    pub instance: InputInfo,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub index: Option<BufferId>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub index_format: IndexFormat,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub streams: Vec<VertexStream>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct InputInfo {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VertexStream {
+    pub buffer: BufferId,
+    pub step_mode: StepMode,
     pub attributes: Vec<InputAttribute>,
     pub stride: u64,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepMode {
+    Vertex,
+    Instance,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputAttribute {
     pub item: InputItem,
     pub offset: u64,
@@ -157,6 +618,7 @@ pub struct InputAttribute {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InputItem {
     Float16x2,
     Float16x4,
@@ -191,44 +653,83 @@ pub enum InputItem {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BindGroup {
-    pub uniform: Option<Binding<UniformInfo>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub uniforms: Vec<Binding<UniformInfo>>,
     pub textures: Vec<Binding<TextureInfo>>,
     pub samplers: Vec<Binding<SamplerInfo>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub storage_buffers: Vec<Binding<StorageBufferInfo>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub storage_textures: Vec<Binding<StorageTextureInfo>>,
 }
 
 impl BindGroup {
     pub fn has_uniform(&self) -> bool {
-        self.uniform.is_some()
+        !self.uniforms.is_empty()
+    }
+
+    pub fn has_buffer(&self, index: BufferId) -> bool {
+        let uniform = self
+            .uniforms
+            .iter()
+            .any(|binding| binding.info.buffer == index);
+        let storage = self
+            .storage_buffers
+            .iter()
+            .any(|binding| binding.info.buffer == index);
+        uniform || storage
     }
 
-    pub fn has_texture(&self, index: usize) -> bool {
+    pub fn has_texture(&self, index: TextureId) -> bool {
         self.textures
             .iter()
             .any(|binding| binding.info.texture == index)
     }
 
-    pub fn has_sampler(&self, index: usize) -> bool {
+    pub fn has_sampler(&self, index: SamplerId) -> bool {
         self.samplers
             .iter()
             .any(|binding| binding.info.sampler == index)
     }
 
+    pub fn has_storage_texture(&self, index: TextureId) -> bool {
+        self.storage_textures
+            .iter()
+            .any(|binding| binding.info.texture == index)
+    }
+
     pub fn bindings_count(&self) -> usize {
         self.textures.len()
             + self.samplers.len()
-            + self.uniform.as_ref().map(|_| 1).unwrap_or_default()
+            + self.storage_buffers.len()
+            + self.storage_textures.len()
+            + self.uniforms.len()
     }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShaderStages {
     Vertex,
     Fragment,
     Both,
+    Compute,
+}
+
+/// Bytes of push constants, visible to shader stages. Each stage can be used in one range only.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PushConstantRange {
+    pub stages: ShaderStages,
+    pub range: Range<u32>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Binding<BindingInfo> {
     pub index: u32,
     pub shader_stages: ShaderStages,
@@ -236,23 +737,103 @@ pub struct Binding<BindingInfo> {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UniformInfo {
+    pub buffer: BufferId,
     pub size: u64,
     pub offset: u64,
+    /// Offset is shifted by value, passed with `DrawData::BindGroup`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dynamic_offset: bool,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextureInfo {
-    pub texture: usize,
+    pub texture: TextureId,
     pub dimension: TextureViewDimension,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub range: ViewRange,
+}
+
+/// Mip levels, array layers and aspect of texture, visible through view.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ViewRange {
+    /// Depth or stencil aspect of depth-stencil texture can be sampled separately.
+    pub aspect: TextureAspect,
+    pub base_mip_level: u32,
+    /// All levels starting from `base_mip_level`, if `None`.
+    pub mip_level_count: Option<u32>,
+    pub base_array_layer: u32,
+    /// All layers starting from `base_array_layer`, if `None`.
+    pub array_layer_count: Option<u32>,
+}
+
+impl ViewRange {
+    /// Viewed mip levels of texture with `mip_levels` levels. `None`, if end of range overflows.
+    pub fn mip_levels(&self, mip_levels: u32) -> Option<Range<u32>> {
+        let end = match self.mip_level_count {
+            Some(count) => self.base_mip_level.checked_add(count)?,
+            None => mip_levels,
+        };
+        Some(self.base_mip_level..end)
+    }
+
+    /// Viewed array layers of texture with `layers` layers. `None`, if end of range overflows.
+    pub fn array_layers(&self, layers: u32) -> Option<Range<u32>> {
+        let end = match self.array_layer_count {
+            Some(count) => self.base_array_layer.checked_add(count)?,
+            None => layers,
+        };
+        Some(self.base_array_layer..end)
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SamplerInfo {
-    pub sampler: usize,
+    pub sampler: SamplerId,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageBufferInfo {
+    pub buffer: BufferId,
+    pub offset: u64,
+    /// Size of bound range. `None` binds whole buffer after `offset`.
+    pub size: Option<u64>,
+    pub read_only: bool,
+    /// Offset is shifted by value, passed with `DrawData::BindGroup`. Requires `size`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dynamic_offset: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageTextureInfo {
+    pub texture: TextureId,
+    pub dimension: TextureViewDimension,
+    pub access: StorageAccess,
+    /// Must select single mip level.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub range: ViewRange,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageAccess {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size2d {
     pub x: u32,
     pub y: u32,
@@ -286,42 +867,211 @@ impl Size2d {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Texture {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
     pub typ: TextureType,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dimension: TextureDimension,
+    /// Depth of 3D texture or number of layers of 2D texture. 1D texture has height and depth of 1.
     pub size: Extent3d,
     pub mip_levels: u32,
-    pub multisampled: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sample_count: SampleCount,
     pub window_source: bool,
     pub writable: bool,
+    /// Regenerate mip levels after each write to level 0. Requires `writable`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_mipmaps: bool,
+}
+
+impl Texture {
+    /// Number of array layers. Depth of 3D texture is not counted.
+    pub fn array_layers(&self) -> u32 {
+        match self.dimension {
+            TextureDimension::D3 => 1,
+            _ => self.size.depth_or_array_layers,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureType {
     Color {
         texel: TexelType,
         texel_count: TexelCount,
     },
+    /// 8-bit RGBA in sRGB color space.
     Srgb,
-    Depth,
+    /// 8-bit BGRA, as used by most of window surfaces.
+    Bgra {
+        srgb: bool,
+    },
+    Packed(PackedFormat),
+    /// Can't be used as attachment. Size must be multiple of block size.
+    Compressed(CompressedFormat),
+    Depth(DepthFormat),
 }
 
+/// Block-compressed formats. BC, ETC2/EAC and ASTC formats require `TEXTURE_COMPRESSION_BC`,
+/// `TEXTURE_COMPRESSION_ETC2` and `TEXTURE_COMPRESSION_ASTC_LDR` device features respectively.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompressedFormat {
+    Bc1 { srgb: bool },
+    Bc2 { srgb: bool },
+    Bc3 { srgb: bool },
+    Bc4 { signed: bool },
+    Bc5 { signed: bool },
+    Bc6h { signed: bool },
+    Bc7 { srgb: bool },
+    Etc2Rgb8 { srgb: bool },
+    Etc2Rgb8A1 { srgb: bool },
+    Etc2Rgba8 { srgb: bool },
+    EacR11 { signed: bool },
+    EacRg11 { signed: bool },
+    Astc { block: AstcBlock, srgb: bool },
+}
+
+impl CompressedFormat {
+    /// Width and height of block in texels.
+    pub fn block_size(&self) -> (u32, u32) {
+        match self {
+            Self::Astc { block, .. } => block.size(),
+            _ => (4, 4),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AstcBlock {
+    B4x4,
+    B5x4,
+    B5x5,
+    B6x5,
+    B6x6,
+    B8x5,
+    B8x6,
+    B8x8,
+    B10x5,
+    B10x6,
+    B10x8,
+    B10x10,
+    B12x10,
+    B12x12,
+}
+
+impl AstcBlock {
+    /// Width and height of block in texels.
+    pub fn size(&self) -> (u32, u32) {
+        match self {
+            Self::B4x4 => (4, 4),
+            Self::B5x4 => (5, 4),
+            Self::B5x5 => (5, 5),
+            Self::B6x5 => (6, 5),
+            Self::B6x6 => (6, 6),
+            Self::B8x5 => (8, 5),
+            Self::B8x6 => (8, 6),
+            Self::B8x8 => (8, 8),
+            Self::B10x5 => (10, 5),
+            Self::B10x6 => (10, 6),
+            Self::B10x8 => (10, 8),
+            Self::B10x10 => (10, 10),
+            Self::B12x10 => (12, 10),
+            Self::B12x12 => (12, 12),
+        }
+    }
+}
+
+/// Formats with channels of different sizes packed into 32 bits.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PackedFormat {
+    /// 10-bit RGB and 2-bit alpha unorm.
+    Rgb10a2Unorm,
+    /// 11-bit RG and 10-bit B float, e.g. for HDR light accumulation.
+    Rg11b10Float,
+    /// 9-bit RGB with shared 5-bit exponent. Can't be used as attachment.
+    Rgb9e5Ufloat,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextureDimension {
+    D1,
+    #[default]
+    D2,
+    D3,
+}
+
+impl TextureDimension {
+    /// Checks if texture of this dimension can be viewed with `view` dimension.
+    pub fn is_compatible(&self, view: TextureViewDimension) -> bool {
+        match self {
+            Self::D1 => view == TextureViewDimension::D1,
+            Self::D2 => matches!(
+                view,
+                TextureViewDimension::D2
+                    | TextureViewDimension::D2Array
+                    | TextureViewDimension::Cube
+                    | TextureViewDimension::CubeArray
+            ),
+            Self::D3 => view == TextureViewDimension::D3,
+        }
+    }
+}
+
+impl TextureType {
+    pub fn is_depth(&self) -> bool {
+        matches!(self, Self::Depth(_))
+    }
+}
+
+/// Format of depth texture. `Depth16Unorm` and `Depth32FloatStencil8` are not supported by
+/// current wgpu version.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DepthFormat {
+    Depth32Float,
+    Depth24Plus,
+    Depth24PlusStencil8,
+}
+
+impl DepthFormat {
+    pub fn has_stencil(&self) -> bool {
+        matches!(self, Self::Depth24PlusStencil8)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TexelType {
     Float(BytesPerFloatTexel),
     Sint(BytesPerIntTexel),
     Uint(BytesPerIntTexel),
+    /// 8-bit signed normalized.
     Snorm,
+    /// 8-bit unsigned normalized.
     Unorm,
+    /// 16-bit signed normalized. Requires `TEXTURE_FORMAT_16BIT_NORM` device feature.
+    Snorm16,
+    /// 16-bit unsigned normalized. Requires `TEXTURE_FORMAT_16BIT_NORM` device feature.
+    Unorm16,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BytesPerFloatTexel {
     Two,
     Four,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BytesPerIntTexel {
     One,
     Two,
@@ -329,53 +1079,119 @@ pub enum BytesPerIntTexel {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TexelCount {
     One,
     Two,
     Four,
 }
 
-#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sampler {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
     pub typ: SamplerType,
+    /// Filters implied by `typ`, if `None`: linear for `Filter` and nearest for others.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub filters: Option<Filters>,
     pub u_address: AddressMode,
     pub v_address: AddressMode,
     pub w_address: AddressMode,
+    /// Used by `AddressMode::Border` axes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub border_color: BorderColor,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lod_clamp: LodClamp,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+impl Sampler {
+    pub fn has_border(&self) -> bool {
+        [self.u_address, self.v_address, self.w_address].contains(&AddressMode::Border)
+    }
+}
+
+/// `NonFilter` samplers must use nearest filters only.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Filters {
+    pub min: Filter,
+    pub mag: Filter,
+    pub mipmap: Filter,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Filter {
+    #[default]
+    Nearest,
+    Linear,
+}
+
+/// Range of mip levels available for sampling. LOD bias is not exposed by wgpu.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct LodClamp {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Default for LodClamp {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: f32::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AddressMode {
+    #[default]
     Edge,
     Repeat,
     Mirror,
+    /// Requires `ADDRESS_MODE_CLAMP_TO_BORDER` device feature.
+    Border,
 }
 
-impl Default for AddressMode {
-    fn default() -> Self {
-        Self::Edge
-    }
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BorderColor {
+    #[default]
+    TransparentBlack,
+    OpaqueBlack,
+    OpaqueWhite,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerType {
+    #[default]
     NonFilter,
     Filter(FilterMode),
     Comparison(CompareMode),
 }
 
-impl Default for SamplerType {
-    fn default() -> Self {
-        Self::NonFilter
-    }
-}
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FilterMode {
     Linear,
+    /// Uses linear filters, unless `Sampler::filters` are set explicitly.
     Anisotropic(AnisotropyLevel),
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnisotropyLevel {
     One,
     Two,
@@ -385,6 +1201,7 @@ pub enum AnisotropyLevel {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CompareMode {
     Never,
     Less,
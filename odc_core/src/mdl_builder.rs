@@ -0,0 +1,55 @@
+use crate::mdl::{
+    BindGroup, BindGroupId, Buffer, BufferId, ComputePipeline, ComputePipelineId, Pass, PassId,
+    PipelineId, RenderModel, RenderPipeline, Sampler, SamplerId, Texture, TextureId,
+};
+
+/// Collects model items and hands out typed ids to reference them from other items.
+#[derive(Debug, Clone, Default)]
+pub struct RenderModelBuilder {
+    model: RenderModel,
+}
+
+impl RenderModelBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_buffer(&mut self, buffer: Buffer) -> BufferId {
+        self.model.buffers.push(buffer);
+        BufferId(self.model.buffers.len() - 1)
+    }
+
+    pub fn add_texture(&mut self, texture: Texture) -> TextureId {
+        self.model.textures.push(texture);
+        TextureId(self.model.textures.len() - 1)
+    }
+
+    pub fn add_sampler(&mut self, sampler: Sampler) -> SamplerId {
+        self.model.samplers.push(sampler);
+        SamplerId(self.model.samplers.len() - 1)
+    }
+
+    pub fn add_bind_group(&mut self, bind_group: BindGroup) -> BindGroupId {
+        self.model.bind_groups.push(bind_group);
+        BindGroupId(self.model.bind_groups.len() - 1)
+    }
+
+    pub fn add_pipeline(&mut self, pipeline: RenderPipeline) -> PipelineId {
+        self.model.pipelines.push(pipeline);
+        PipelineId(self.model.pipelines.len() - 1)
+    }
+
+    pub fn add_compute_pipeline(&mut self, pipeline: ComputePipeline) -> ComputePipelineId {
+        self.model.compute_pipelines.push(pipeline);
+        ComputePipelineId(self.model.compute_pipelines.len() - 1)
+    }
+
+    pub fn add_pass(&mut self, pass: Pass) -> PassId {
+        self.model.passes.push(pass);
+        PassId(self.model.passes.len() - 1)
+    }
+
+    pub fn build(self) -> RenderModel {
+        self.model
+    }
+}
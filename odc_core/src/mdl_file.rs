@@ -0,0 +1,159 @@
+use crate::mdl::RenderModel;
+use crate::{OdcError, OdcResult};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Loading and saving of render models.
+/// Relative shader paths in model files are resolved relative to the model file directory.
+/// Shader paths are saved relative to the model file directory, if they are inside it,
+/// and absolute otherwise.
+impl RenderModel {
+    pub fn from_ron_file(path: impl AsRef<Path>) -> OdcResult<Self> {
+        let path = path.as_ref();
+        let source = read_model_file(path)?;
+        let model = ron::from_str(&source).map_err(OdcError::Ron)?;
+        Ok(resolve_shader_paths(model, path))
+    }
+
+    pub fn from_json_file(path: impl AsRef<Path>) -> OdcResult<Self> {
+        let path = path.as_ref();
+        let source = read_model_file(path)?;
+        let model = serde_json::from_str(&source).map_err(OdcError::Json)?;
+        Ok(resolve_shader_paths(model, path))
+    }
+
+    pub fn save_ron_file(&self, path: impl AsRef<Path>) -> OdcResult<()> {
+        let path = path.as_ref();
+        let model = relative_shader_paths(self.clone(), path);
+        let config = ron::ser::PrettyConfig::default();
+        let source = ron::ser::to_string_pretty(&model, config).map_err(OdcError::Ron)?;
+        write_model_file(path, source)
+    }
+
+    pub fn save_json_file(&self, path: impl AsRef<Path>) -> OdcResult<()> {
+        let path = path.as_ref();
+        let model = relative_shader_paths(self.clone(), path);
+        let source = serde_json::to_string_pretty(&model).map_err(OdcError::Json)?;
+        write_model_file(path, source)
+    }
+}
+
+fn read_model_file(path: &Path) -> OdcResult<String> {
+    fs::read_to_string(path).map_err(|e| OdcError::ModelFile {
+        path: path.into(),
+        source: e,
+    })
+}
+
+fn write_model_file(path: &Path, source: String) -> OdcResult<()> {
+    fs::write(path, source).map_err(|e| OdcError::ModelFile {
+        path: path.into(),
+        source: e,
+    })
+}
+
+fn model_dir(model_path: &Path) -> PathBuf {
+    model_path.parent().map(Into::into).unwrap_or_default()
+}
+
+/// Makes `path` relative to `dir`, if it's inside `dir`, or absolute otherwise.
+fn relative_path(path: &Path, dir: &Path) -> PathBuf {
+    let (path, dir) = match env::current_dir() {
+        Ok(current_dir) => (current_dir.join(path), current_dir.join(dir)),
+        Err(_) => return path.into(),
+    };
+    match path.strip_prefix(&dir) {
+        Ok(relative) => relative.into(),
+        Err(_) => path,
+    }
+}
+
+fn resolve_shader_paths(mut model: RenderModel, model_path: &Path) -> RenderModel {
+    let dir = model_dir(model_path);
+    for pipeline in model.pipelines.iter_mut() {
+        pipeline.shader.path = dir.join(&pipeline.shader.path);
+    }
+    for pipeline in model.compute_pipelines.iter_mut() {
+        pipeline.shader.path = dir.join(&pipeline.shader.path);
+    }
+    model
+}
+
+fn relative_shader_paths(mut model: RenderModel, model_path: &Path) -> RenderModel {
+    let dir = model_dir(model_path);
+    for pipeline in model.pipelines.iter_mut() {
+        pipeline.shader.path = relative_path(&pipeline.shader.path, &dir);
+    }
+    for pipeline in model.compute_pipelines.iter_mut() {
+        pipeline.shader.path = relative_path(&pipeline.shader.path, &dir);
+    }
+    model
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mdl::{ComputePipeline, ComputeShader, RenderModel};
+    use crate::mdl_validate::tests::model;
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir()
+            .join(format!("odc_model_file_{}", std::process::id()))
+            .join(name);
+        fs::create_dir_all(dir.join("shaders")).unwrap();
+        dir
+    }
+
+    /// Model with one shader inside of `dir` and one shader relative to working directory.
+    fn shader_model(dir: &Path) -> RenderModel {
+        let mut model = model();
+        model.pipelines[0].shader.path = dir.join("shaders/inner.wgsl");
+        model.compute_pipelines.push(ComputePipeline {
+            name: Some("compute".into()),
+            bind_groups: vec![],
+            shader: ComputeShader {
+                path: "shaders/outer.wgsl".into(),
+                cs_main: "cs_main".into(),
+            },
+        });
+        model
+    }
+
+    fn check_roundtrip(saved: &RenderModel, loaded: &RenderModel) {
+        let mut expected = saved.clone();
+        let shader = &mut expected.compute_pipelines[0].shader;
+        shader.path = env::current_dir().unwrap().join(&shader.path);
+        assert_eq!(*loaded, expected);
+    }
+
+    #[test]
+    fn ron_roundtrip() {
+        let dir = temp_dir("ron");
+        let file = dir.join("model.ron");
+        let model = shader_model(&dir);
+        model.save_ron_file(&file).unwrap();
+
+        let source = fs::read_to_string(&file).unwrap();
+        assert!(source.contains("\"shaders/inner.wgsl\""));
+
+        let loaded = RenderModel::from_ron_file(&file).unwrap();
+        check_roundtrip(&model, &loaded);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let dir = temp_dir("json");
+        let file = dir.join("model.json");
+        let model = shader_model(&dir);
+        model.save_json_file(&file).unwrap();
+
+        let source = fs::read_to_string(&file).unwrap();
+        assert!(source.contains("\"shaders/inner.wgsl\""));
+
+        let loaded = RenderModel::from_json_file(&file).unwrap();
+        check_roundtrip(&model, &loaded);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,14 +1,17 @@
 use crate::mdl;
 use crate::mdl::AddressMode;
 use crate::pipelines::{
-    InputBufferLayout, RenderPipelineInfo, RenderPipelineInput, RenderShaderInfo,
+    ComputePipelineInfo, ComputeShaderInfo, InputBufferLayout, RenderPipelineInfo,
+    RenderPipelineInput, RenderShaderInfo,
 };
 use crate::res::{
-    BindGroupInfo, Binding, BufferInfo, SamplerBindingInfo, SamplerInfo, TextureBindingInfo,
-    TextureInfo, UniformBindingInfo,
+    BindGroupInfo, Binding, BufferInfo, SamplerBindingInfo, SamplerInfo, StorageBufferBindingInfo,
+    StorageTextureBindingInfo, TextureBindingInfo, TextureInfo, UniformBindingInfo,
 };
+use crate::{OdcError, OdcResult};
 use std::fs;
-use std::num::NonZeroU8;
+use std::num::{NonZeroU32, NonZeroU8};
+use std::path::Path;
 
 pub struct ModelParser<'a> {
     model: &'a mdl::RenderModel,
@@ -19,78 +22,170 @@ impl<'a> ModelParser<'a> {
         Self { model }
     }
 
-    pub fn index_info(&self) -> BufferInfo {
-        BufferInfo {
-            size: self.model.buffers.index,
-            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-        }
+    pub fn buffers_info(&self) -> impl Iterator<Item = BufferInfo> + 'a {
+        self.model.buffers.iter().map(|buffer| BufferInfo {
+            name: buffer.name.clone(),
+            size: buffer.size,
+            usage: Self::parse_buffer_usage(buffer.usage),
+        })
     }
 
-    pub fn vertex_info(&self) -> BufferInfo {
-        BufferInfo {
-            size: self.model.buffers.vertex,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+    /// Device features, which model can't work without.
+    pub fn required_features(&self) -> wgpu::Features {
+        let mut features = wgpu::Features::empty();
+        for pipeline in self.model.pipelines.iter() {
+            let primitive = &pipeline.primitive;
+            match primitive.polygon_mode {
+                mdl::PolygonMode::Fill => {}
+                mdl::PolygonMode::Line => features |= wgpu::Features::POLYGON_MODE_LINE,
+                mdl::PolygonMode::Point => features |= wgpu::Features::POLYGON_MODE_POINT,
+            }
+
+            if primitive.unclipped_depth {
+                features |= wgpu::Features::DEPTH_CLIP_CONTROL;
+            }
+
+            if !pipeline.push_constants.is_empty() {
+                features |= wgpu::Features::PUSH_CONSTANTS;
+            }
         }
-    }
 
-    pub fn instance_info(&self) -> BufferInfo {
-        BufferInfo {
-            size: self.model.buffers.instance,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        // Storage textures with read access are adapter specific in wgpu.
+        let storage_read = self
+            .model
+            .bind_groups
+            .iter()
+            .flat_map(|bind_group| bind_group.storage_textures.iter())
+            .any(|binding| binding.info.access != mdl::StorageAccess::WriteOnly);
+        if storage_read {
+            features |= wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+        }
+
+        if self.model.samplers.iter().any(mdl::Sampler::has_border) {
+            features |= wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER;
         }
-    }
 
-    pub fn uniform_info(&self) -> BufferInfo {
-        BufferInfo {
-            size: self.model.buffers.index,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        for texture in self.model.textures.iter() {
+            let format = Self::parse_texture_format(texture.typ);
+            features |= format.describe().required_features;
         }
+        features
     }
 
     pub fn textures_info(&self) -> impl Iterator<Item = TextureInfo> + 'a {
         let model = self.model;
         model.textures.iter().enumerate().map(|(i, texture_model)| {
+            let i = mdl::TextureId(i);
             let mut usages = wgpu::TextureUsages::empty();
             if model.has_texture_binding(i) || texture_model.window_source {
                 usages |= wgpu::TextureUsages::TEXTURE_BINDING;
             }
 
+            if model.has_storage_texture_binding(i) {
+                usages |= wgpu::TextureUsages::STORAGE_BINDING;
+            }
+
             if model.has_texture_attachment(i) {
                 usages |= wgpu::TextureUsages::RENDER_ATTACHMENT;
             }
 
             if texture_model.writable {
                 usages |= wgpu::TextureUsages::COPY_DST;
+                if texture_model.mip_levels > 1 && Self::supports_mipmaps(texture_model) {
+                    usages |= wgpu::TextureUsages::TEXTURE_BINDING
+                        | wgpu::TextureUsages::RENDER_ATTACHMENT;
+                }
             }
 
-            let sample_count = if texture_model.multisampled { 4 } else { 1 };
-
             TextureInfo {
+                name: texture_model.name.clone(),
                 format: Self::parse_texture_format(texture_model.typ),
+                dimension: Self::parse_texture_dimension(texture_model.dimension),
                 size: texture_model.size,
                 mip_levels: texture_model.mip_levels,
-                sample_count,
+                sample_count: texture_model.sample_count.count(),
                 usages,
             }
         })
     }
 
+    /// Formats of textures, which mip levels can be generated.
+    pub fn mipmap_formats(&self) -> impl Iterator<Item = wgpu::TextureFormat> + 'a {
+        self.model
+            .textures
+            .iter()
+            .filter(|texture| texture.writable && texture.mip_levels > 1)
+            .filter(|texture| Self::supports_mipmaps(texture))
+            .map(|texture| Self::parse_texture_format(texture.typ))
+    }
+
+    /// Mip levels are generated by rendering, so format must be filterable and renderable.
+    pub fn supports_mipmaps(texture: &mdl::Texture) -> bool {
+        let format = Self::parse_texture_format(texture.typ).describe();
+        texture.dimension == mdl::TextureDimension::D2
+            && texture.sample_count == mdl::SampleCount::One
+            && format.guaranteed_format_features.filterable
+            && format.sample_type == wgpu::TextureSampleType::Float { filterable: true }
+            && format
+                .guaranteed_format_features
+                .allowed_usages
+                .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+    }
+
     pub fn samplers_info(&self) -> impl Iterator<Item = SamplerInfo> + 'a {
-        self.model.samplers.iter().map(|sampler_model| SamplerInfo {
-            mode: Self::parse_filter_mode(sampler_model.typ),
-            compare: Self::parse_comparison(sampler_model.typ),
-            anisotropy: Self::parse_anisotropy(sampler_model.typ),
-            u_address: Self::parse_address_mode(sampler_model.u_address),
-            v_address: Self::parse_address_mode(sampler_model.v_address),
-            w_address: Self::parse_address_mode(sampler_model.w_address),
+        self.model.samplers.iter().map(|sampler_model| {
+            let filters = sampler_model.filters.unwrap_or_else(|| {
+                let filter = Self::implied_filter(sampler_model.typ);
+                mdl::Filters {
+                    min: filter,
+                    mag: filter,
+                    mipmap: filter,
+                }
+            });
+
+            let border_color = if sampler_model.has_border() {
+                Some(Self::parse_border_color(sampler_model.border_color))
+            } else {
+                None
+            };
+
+            SamplerInfo {
+                name: sampler_model.name.clone(),
+                min_filter: Self::parse_filter(filters.min),
+                mag_filter: Self::parse_filter(filters.mag),
+                mipmap_filter: Self::parse_filter(filters.mipmap),
+                lod_min_clamp: sampler_model.lod_clamp.min,
+                lod_max_clamp: sampler_model.lod_clamp.max,
+                compare: Self::parse_comparison(sampler_model.typ),
+                anisotropy: Self::parse_anisotropy(sampler_model.typ),
+                u_address: Self::parse_address_mode(sampler_model.u_address),
+                v_address: Self::parse_address_mode(sampler_model.v_address),
+                w_address: Self::parse_address_mode(sampler_model.w_address),
+                border_color,
+            }
         })
     }
 
-    pub fn parse_filter_mode(sampler_model: mdl::SamplerType) -> wgpu::FilterMode {
+    fn implied_filter(sampler_model: mdl::SamplerType) -> mdl::Filter {
         match sampler_model {
-            mdl::SamplerType::NonFilter => wgpu::FilterMode::Nearest,
-            mdl::SamplerType::Filter(_) => wgpu::FilterMode::Linear,
-            mdl::SamplerType::Comparison(_) => wgpu::FilterMode::Nearest,
+            mdl::SamplerType::NonFilter => mdl::Filter::Nearest,
+            mdl::SamplerType::Filter(_) => mdl::Filter::Linear,
+            mdl::SamplerType::Comparison(_) => mdl::Filter::Nearest,
+        }
+    }
+
+    fn parse_filter(filter: mdl::Filter) -> wgpu::FilterMode {
+        match filter {
+            mdl::Filter::Nearest => wgpu::FilterMode::Nearest,
+            mdl::Filter::Linear => wgpu::FilterMode::Linear,
+        }
+    }
+
+    fn parse_border_color(color: mdl::BorderColor) -> wgpu::SamplerBorderColor {
+        match color {
+            mdl::BorderColor::TransparentBlack => wgpu::SamplerBorderColor::TransparentBlack,
+            mdl::BorderColor::OpaqueBlack => wgpu::SamplerBorderColor::OpaqueBlack,
+            mdl::BorderColor::OpaqueWhite => wgpu::SamplerBorderColor::OpaqueWhite,
         }
     }
 
@@ -132,20 +227,27 @@ impl<'a> ModelParser<'a> {
             AddressMode::Edge => wgpu::AddressMode::ClampToEdge,
             AddressMode::Repeat => wgpu::AddressMode::Repeat,
             AddressMode::Mirror => wgpu::AddressMode::MirrorRepeat,
+            AddressMode::Border => wgpu::AddressMode::ClampToBorder,
         }
     }
 
     pub fn bind_groups_info(&self) -> impl Iterator<Item = BindGroupInfo> + 'a {
         let model = self.model;
         model.bind_groups.iter().map(|bg| {
-            let uniform = bg.uniform.as_ref().map(|uniform_model| Binding {
-                index: uniform_model.index,
-                visibility: Self::parse_visibility(uniform_model.shader_stages),
-                info: UniformBindingInfo {
-                    size: uniform_model.info.size,
-                    offset: uniform_model.info.offset,
-                },
-            });
+            let uniforms = bg
+                .uniforms
+                .iter()
+                .map(|uniform_model| Binding {
+                    index: uniform_model.index,
+                    visibility: Self::parse_visibility(uniform_model.shader_stages),
+                    info: UniformBindingInfo {
+                        buffer_index: uniform_model.info.buffer.0,
+                        size: uniform_model.info.size,
+                        offset: uniform_model.info.offset,
+                        dynamic_offset: uniform_model.info.dynamic_offset,
+                    },
+                })
+                .collect();
 
             let textures = bg
                 .textures
@@ -155,10 +257,11 @@ impl<'a> ModelParser<'a> {
                     visibility: Self::parse_visibility(texture_model.shader_stages),
                     info: TextureBindingInfo {
                         format: Self::parse_texture_format(
-                            model.textures[texture_model.info.texture].typ,
+                            model.textures[texture_model.info.texture.0].typ,
                         ),
-                        texture_index: texture_model.info.texture,
+                        texture_index: texture_model.info.texture.0,
                         dimension: texture_model.info.dimension,
+                        range: Self::parse_view_range(texture_model.info.range),
                     },
                 })
                 .collect();
@@ -167,8 +270,8 @@ impl<'a> ModelParser<'a> {
                 .samplers
                 .iter()
                 .map(|sampler_model| {
-                    let sampler_index = sampler_model.info.sampler;
-                    let sampler = model.samplers[sampler_index];
+                    let sampler_index = sampler_model.info.sampler.0;
+                    let sampler = &model.samplers[sampler_index];
                     Binding {
                         index: sampler_model.index,
                         visibility: Self::parse_visibility(sampler_model.shader_stages),
@@ -180,88 +283,267 @@ impl<'a> ModelParser<'a> {
                 })
                 .collect();
 
+            let storage_buffers = bg
+                .storage_buffers
+                .iter()
+                .map(|buffer_model| Binding {
+                    index: buffer_model.index,
+                    visibility: Self::parse_visibility(buffer_model.shader_stages),
+                    info: StorageBufferBindingInfo {
+                        buffer_index: buffer_model.info.buffer.0,
+                        offset: buffer_model.info.offset,
+                        size: buffer_model.info.size,
+                        read_only: buffer_model.info.read_only,
+                        dynamic_offset: buffer_model.info.dynamic_offset,
+                    },
+                })
+                .collect();
+
+            let storage_textures = bg
+                .storage_textures
+                .iter()
+                .map(|texture_model| Binding {
+                    index: texture_model.index,
+                    visibility: Self::parse_visibility(texture_model.shader_stages),
+                    info: StorageTextureBindingInfo {
+                        format: Self::parse_texture_format(
+                            model.textures[texture_model.info.texture.0].typ,
+                        ),
+                        texture_index: texture_model.info.texture.0,
+                        dimension: texture_model.info.dimension,
+                        access: Self::parse_storage_access(texture_model.info.access),
+                        range: Self::parse_view_range(texture_model.info.range),
+                    },
+                })
+                .collect();
+
             BindGroupInfo {
-                uniform,
+                name: bg.name.clone(),
+                uniforms,
                 textures,
                 samplers,
+                storage_buffers,
+                storage_textures,
             }
         })
     }
 
-    pub fn render_pipelines_info(&self) -> impl Iterator<Item = RenderPipelineInfo> + 'a {
+    pub fn render_pipelines_info(
+        &self,
+    ) -> impl Iterator<Item = OdcResult<RenderPipelineInfo>> + 'a {
         let model = self.model;
         model.pipelines.iter().enumerate().map(|(i, info)| {
-            let source = fs::read_to_string(&info.shader.path).expect("shader file not found");
+            let source = Self::read_shader(&info.shader.path)?;
             let shader = RenderShaderInfo {
                 source,
                 vs_main: info.shader.vs_main.clone(),
                 fs_main: info.shader.fs_main.clone(),
             };
 
-            let sample_count = if info.multisampled { 4 } else { 1 };
-
-            RenderPipelineInfo {
+            Ok(RenderPipelineInfo {
+                name: info.name.clone(),
                 shader,
                 input: Self::input_buffers_info(info),
-                bind_groups: info.bind_groups.clone(),
-                depth_test: info.depth.is_some(),
-                color_targets: Self::pipeline_color_targets(model, i),
-                sample_count,
-            }
+                bind_groups: info.bind_groups.iter().map(|id| id.0).collect(),
+                push_constant_ranges: info
+                    .push_constants
+                    .iter()
+                    .map(|push_constants| wgpu::PushConstantRange {
+                        stages: Self::parse_visibility(push_constants.stages),
+                        range: push_constants.range.clone(),
+                    })
+                    .collect(),
+                depth_stencil: Self::pipeline_depth_stencil(model, mdl::PipelineId(i))?,
+                color_targets: Self::pipeline_color_targets(model, mdl::PipelineId(i))?,
+                multisample: Self::parse_multisample(info.multisample),
+                primitive: Self::parse_primitive(&info.primitive),
+            })
+        })
+    }
+
+    pub fn compute_pipelines_info(
+        &self,
+    ) -> impl Iterator<Item = OdcResult<ComputePipelineInfo>> + 'a {
+        self.model.compute_pipelines.iter().map(|info| {
+            let source = Self::read_shader(&info.shader.path)?;
+            let shader = ComputeShaderInfo {
+                source,
+                cs_main: info.shader.cs_main.clone(),
+            };
+
+            Ok(ComputePipelineInfo {
+                name: info.name.clone(),
+                shader,
+                bind_groups: info.bind_groups.iter().map(|id| id.0).collect(),
+            })
+        })
+    }
+
+    fn read_shader(path: &Path) -> OdcResult<String> {
+        fs::read_to_string(path).map_err(|e| OdcError::ShaderNotFound {
+            path: path.into(),
+            source: e,
         })
     }
 
     fn pipeline_color_targets(
         model: &mdl::RenderModel,
-        pipeline_index: usize,
-    ) -> Vec<wgpu::ColorTargetState> {
+        pipeline_index: mdl::PipelineId,
+    ) -> OdcResult<Vec<wgpu::ColorTargetState>> {
         for pass in model.passes.iter() {
-            if pass.pipelines.iter().any(|p| *p == pipeline_index) {
-                return pass
+            if pass.pipelines.contains(&pipeline_index) {
+                return Ok(pass
                     .color_attachments
                     .iter()
-                    .zip(model.pipelines[pipeline_index].blend.iter().copied())
-                    .map(|(attachment, blend)| {
-                        let texture_type = model.textures[attachment.texture].typ;
+                    .zip(model.pipelines[pipeline_index.0].targets.iter())
+                    .map(|(attachment, target)| {
+                        let texture_type = model.textures[attachment.texture.0].typ;
                         let format = Self::parse_texture_format(texture_type);
                         wgpu::ColorTargetState {
                             format,
-                            blend,
-                            write_mask: Default::default(),
+                            blend: target.blend,
+                            write_mask: target.write_mask,
                         }
                     })
-                    .collect();
+                    .collect());
             }
         }
-        panic!("pipeline {} is not used in any pass", pipeline_index);
+        Err(OdcError::PipelineNotUsed(pipeline_index))
     }
 
-    fn input_buffers_info(pipeline: &mdl::RenderPipeline) -> Option<RenderPipelineInput> {
-        pipeline.input.as_ref().map(|input| {
-            let vertex = Self::input_buffer_layout(&input.vertex, wgpu::VertexStepMode::Vertex);
-            let instance =
-                Self::input_buffer_layout(&input.instance, wgpu::VertexStepMode::Instance);
-            RenderPipelineInput { vertex, instance }
-        })
+    fn pipeline_depth_stencil(
+        model: &mdl::RenderModel,
+        pipeline_index: mdl::PipelineId,
+    ) -> OdcResult<Option<wgpu::DepthStencilState>> {
+        let ops = match &model.pipelines[pipeline_index.0].depth {
+            Some(ops) => ops,
+            None => return Ok(None),
+        };
+
+        for pass in model.passes.iter() {
+            if pass.pipelines.contains(&pipeline_index) {
+                return Ok(pass.depth_attachment.as_ref().map(|attachment| {
+                    let texture_type = model.textures[attachment.texture.0].typ;
+                    wgpu::DepthStencilState {
+                        format: Self::parse_texture_format(texture_type),
+                        depth_write_enabled: ops.write,
+                        depth_compare: ops.compare,
+                        stencil: ops.stencil.clone(),
+                        bias: ops.bias,
+                    }
+                }));
+            }
+        }
+        Err(OdcError::PipelineNotUsed(pipeline_index))
     }
 
-    fn input_buffer_layout(
-        input_buffer: &mdl::InputInfo,
-        step_mode: wgpu::VertexStepMode,
-    ) -> InputBufferLayout {
-        let attributes = input_buffer
+    fn input_buffers_info(pipeline: &mdl::RenderPipeline) -> RenderPipelineInput {
+        let input = &pipeline.input;
+        let streams = input
+            .streams
+            .iter()
+            .map(Self::input_buffer_layout)
+            .collect();
+
+        RenderPipelineInput {
+            index: input.index.map(|id| id.0),
+            index_format: Self::parse_index_format(input.index_format),
+            streams,
+        }
+    }
+
+    fn input_buffer_layout(stream: &mdl::VertexStream) -> InputBufferLayout {
+        let attributes = stream
             .attributes
             .iter()
             .map(Self::wgpu_input_attributes)
             .collect();
 
+        let step_mode = match stream.step_mode {
+            mdl::StepMode::Vertex => wgpu::VertexStepMode::Vertex,
+            mdl::StepMode::Instance => wgpu::VertexStepMode::Instance,
+        };
+
         InputBufferLayout {
-            stride: input_buffer.stride,
+            buffer_index: stream.buffer.0,
+            stride: stream.stride,
             step_mode,
             attributes,
         }
     }
 
+    fn parse_multisample(multisample: mdl::Multisample) -> wgpu::MultisampleState {
+        wgpu::MultisampleState {
+            count: multisample.count.count(),
+            mask: multisample.mask,
+            alpha_to_coverage_enabled: multisample.alpha_to_coverage,
+        }
+    }
+
+    fn parse_primitive(primitive: &mdl::Primitive) -> wgpu::PrimitiveState {
+        let topology = match primitive.topology {
+            mdl::Topology::PointList => wgpu::PrimitiveTopology::PointList,
+            mdl::Topology::LineList => wgpu::PrimitiveTopology::LineList,
+            mdl::Topology::LineStrip => wgpu::PrimitiveTopology::LineStrip,
+            mdl::Topology::TriangleList => wgpu::PrimitiveTopology::TriangleList,
+            mdl::Topology::TriangleStrip => wgpu::PrimitiveTopology::TriangleStrip,
+        };
+
+        let front_face = match primitive.front_face {
+            mdl::FrontFace::Ccw => wgpu::FrontFace::Ccw,
+            mdl::FrontFace::Cw => wgpu::FrontFace::Cw,
+        };
+
+        let cull_mode = match primitive.cull_mode {
+            mdl::CullMode::None => None,
+            mdl::CullMode::Front => Some(wgpu::Face::Front),
+            mdl::CullMode::Back => Some(wgpu::Face::Back),
+        };
+
+        let polygon_mode = match primitive.polygon_mode {
+            mdl::PolygonMode::Fill => wgpu::PolygonMode::Fill,
+            mdl::PolygonMode::Line => wgpu::PolygonMode::Line,
+            mdl::PolygonMode::Point => wgpu::PolygonMode::Point,
+        };
+
+        wgpu::PrimitiveState {
+            topology,
+            strip_index_format: primitive.strip_index_format.map(Self::parse_index_format),
+            front_face,
+            cull_mode,
+            unclipped_depth: primitive.unclipped_depth,
+            polygon_mode,
+            conservative: false,
+        }
+    }
+
+    pub fn parse_index_format(format: mdl::IndexFormat) -> wgpu::IndexFormat {
+        match format {
+            mdl::IndexFormat::Uint16 => wgpu::IndexFormat::Uint16,
+            mdl::IndexFormat::Uint32 => wgpu::IndexFormat::Uint32,
+        }
+    }
+
+    fn parse_buffer_usage(usage: mdl::BufferUsage) -> wgpu::BufferUsages {
+        let mut usages = wgpu::BufferUsages::COPY_DST;
+        if usage.index {
+            usages |= wgpu::BufferUsages::INDEX;
+        }
+        if usage.vertex {
+            usages |= wgpu::BufferUsages::VERTEX;
+        }
+        if usage.uniform {
+            usages |= wgpu::BufferUsages::UNIFORM;
+        }
+        if usage.storage {
+            usages |= wgpu::BufferUsages::STORAGE;
+        }
+        if usage.indirect {
+            usages |= wgpu::BufferUsages::INDIRECT;
+        }
+        usages
+    }
+
     fn wgpu_input_attributes(attribute: &mdl::InputAttribute) -> wgpu::VertexAttribute {
         let format = match attribute.item {
             mdl::InputItem::Float16x2 => wgpu::VertexFormat::Float16x2,
@@ -303,11 +585,31 @@ impl<'a> ModelParser<'a> {
         }
     }
 
-    fn parse_visibility(model: mdl::ShaderStages) -> wgpu::ShaderStages {
+    /// Bytes of push constants, required by pipelines.
+    pub fn push_constants_size(&self) -> u32 {
+        self.model
+            .pipelines
+            .iter()
+            .flat_map(|pipeline| pipeline.push_constants.iter())
+            .map(|push_constants| push_constants.range.end)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn parse_visibility(model: mdl::ShaderStages) -> wgpu::ShaderStages {
         match model {
             mdl::ShaderStages::Vertex => wgpu::ShaderStages::VERTEX,
             mdl::ShaderStages::Fragment => wgpu::ShaderStages::FRAGMENT,
             mdl::ShaderStages::Both => wgpu::ShaderStages::VERTEX_FRAGMENT,
+            mdl::ShaderStages::Compute => wgpu::ShaderStages::COMPUTE,
+        }
+    }
+
+    fn parse_storage_access(access: mdl::StorageAccess) -> wgpu::StorageTextureAccess {
+        match access {
+            mdl::StorageAccess::ReadOnly => wgpu::StorageTextureAccess::ReadOnly,
+            mdl::StorageAccess::WriteOnly => wgpu::StorageTextureAccess::WriteOnly,
+            mdl::StorageAccess::ReadWrite => wgpu::StorageTextureAccess::ReadWrite,
         }
     }
 
@@ -319,13 +621,108 @@ impl<'a> ModelParser<'a> {
         }
     }
 
-    fn parse_texture_format(typ: mdl::TextureType) -> wgpu::TextureFormat {
+    fn parse_texture_dimension(dimension: mdl::TextureDimension) -> wgpu::TextureDimension {
+        match dimension {
+            mdl::TextureDimension::D1 => wgpu::TextureDimension::D1,
+            mdl::TextureDimension::D2 => wgpu::TextureDimension::D2,
+            mdl::TextureDimension::D3 => wgpu::TextureDimension::D3,
+        }
+    }
+
+    pub fn parse_texture_format(typ: mdl::TextureType) -> wgpu::TextureFormat {
         match typ {
             mdl::TextureType::Color { texel, texel_count } => {
                 Self::parse_color_format(texel, texel_count)
             }
             mdl::TextureType::Srgb => wgpu::TextureFormat::Rgba8UnormSrgb,
-            mdl::TextureType::Depth => wgpu::TextureFormat::Depth32Float,
+            mdl::TextureType::Bgra { srgb: false } => wgpu::TextureFormat::Bgra8Unorm,
+            mdl::TextureType::Bgra { srgb: true } => wgpu::TextureFormat::Bgra8UnormSrgb,
+            mdl::TextureType::Packed(format) => match format {
+                mdl::PackedFormat::Rgb10a2Unorm => wgpu::TextureFormat::Rgb10a2Unorm,
+                mdl::PackedFormat::Rg11b10Float => wgpu::TextureFormat::Rg11b10Float,
+                mdl::PackedFormat::Rgb9e5Ufloat => wgpu::TextureFormat::Rgb9e5Ufloat,
+            },
+            mdl::TextureType::Compressed(format) => Self::parse_compressed_format(format),
+            mdl::TextureType::Depth(format) => match format {
+                mdl::DepthFormat::Depth32Float => wgpu::TextureFormat::Depth32Float,
+                mdl::DepthFormat::Depth24Plus => wgpu::TextureFormat::Depth24Plus,
+                mdl::DepthFormat::Depth24PlusStencil8 => wgpu::TextureFormat::Depth24PlusStencil8,
+            },
+        }
+    }
+
+    pub fn parse_view_range(range: mdl::ViewRange) -> wgpu::ImageSubresourceRange {
+        wgpu::ImageSubresourceRange {
+            aspect: range.aspect,
+            base_mip_level: range.base_mip_level,
+            mip_level_count: range.mip_level_count.and_then(NonZeroU32::new),
+            base_array_layer: range.base_array_layer,
+            array_layer_count: range.array_layer_count.and_then(NonZeroU32::new),
+        }
+    }
+
+    fn parse_compressed_format(format: mdl::CompressedFormat) -> wgpu::TextureFormat {
+        use mdl::AstcBlock as Block;
+        use mdl::CompressedFormat as Compressed;
+        use wgpu::TextureFormat as Format;
+
+        match format {
+            Compressed::Bc1 { srgb: false } => Format::Bc1RgbaUnorm,
+            Compressed::Bc1 { srgb: true } => Format::Bc1RgbaUnormSrgb,
+            Compressed::Bc2 { srgb: false } => Format::Bc2RgbaUnorm,
+            Compressed::Bc2 { srgb: true } => Format::Bc2RgbaUnormSrgb,
+            Compressed::Bc3 { srgb: false } => Format::Bc3RgbaUnorm,
+            Compressed::Bc3 { srgb: true } => Format::Bc3RgbaUnormSrgb,
+            Compressed::Bc4 { signed: false } => Format::Bc4RUnorm,
+            Compressed::Bc4 { signed: true } => Format::Bc4RSnorm,
+            Compressed::Bc5 { signed: false } => Format::Bc5RgUnorm,
+            Compressed::Bc5 { signed: true } => Format::Bc5RgSnorm,
+            Compressed::Bc6h { signed: false } => Format::Bc6hRgbUfloat,
+            Compressed::Bc6h { signed: true } => Format::Bc6hRgbSfloat,
+            Compressed::Bc7 { srgb: false } => Format::Bc7RgbaUnorm,
+            Compressed::Bc7 { srgb: true } => Format::Bc7RgbaUnormSrgb,
+
+            Compressed::Etc2Rgb8 { srgb: false } => Format::Etc2Rgb8Unorm,
+            Compressed::Etc2Rgb8 { srgb: true } => Format::Etc2Rgb8UnormSrgb,
+            Compressed::Etc2Rgb8A1 { srgb: false } => Format::Etc2Rgb8A1Unorm,
+            Compressed::Etc2Rgb8A1 { srgb: true } => Format::Etc2Rgb8A1UnormSrgb,
+            Compressed::Etc2Rgba8 { srgb: false } => Format::Etc2Rgba8Unorm,
+            Compressed::Etc2Rgba8 { srgb: true } => Format::Etc2Rgba8UnormSrgb,
+            Compressed::EacR11 { signed: false } => Format::EacR11Unorm,
+            Compressed::EacR11 { signed: true } => Format::EacR11Snorm,
+            Compressed::EacRg11 { signed: false } => Format::EacRg11Unorm,
+            Compressed::EacRg11 { signed: true } => Format::EacRg11Snorm,
+
+            Compressed::Astc { block, srgb } => match (block, srgb) {
+                (Block::B4x4, false) => Format::Astc4x4RgbaUnorm,
+                (Block::B4x4, true) => Format::Astc4x4RgbaUnormSrgb,
+                (Block::B5x4, false) => Format::Astc5x4RgbaUnorm,
+                (Block::B5x4, true) => Format::Astc5x4RgbaUnormSrgb,
+                (Block::B5x5, false) => Format::Astc5x5RgbaUnorm,
+                (Block::B5x5, true) => Format::Astc5x5RgbaUnormSrgb,
+                (Block::B6x5, false) => Format::Astc6x5RgbaUnorm,
+                (Block::B6x5, true) => Format::Astc6x5RgbaUnormSrgb,
+                (Block::B6x6, false) => Format::Astc6x6RgbaUnorm,
+                (Block::B6x6, true) => Format::Astc6x6RgbaUnormSrgb,
+                (Block::B8x5, false) => Format::Astc8x5RgbaUnorm,
+                (Block::B8x5, true) => Format::Astc8x5RgbaUnormSrgb,
+                (Block::B8x6, false) => Format::Astc8x6RgbaUnorm,
+                (Block::B8x6, true) => Format::Astc8x6RgbaUnormSrgb,
+                (Block::B8x8, false) => Format::Astc8x8RgbaUnorm,
+                (Block::B8x8, true) => Format::Astc8x8RgbaUnormSrgb,
+                (Block::B10x5, false) => Format::Astc10x5RgbaUnorm,
+                (Block::B10x5, true) => Format::Astc10x5RgbaUnormSrgb,
+                (Block::B10x6, false) => Format::Astc10x6RgbaUnorm,
+                (Block::B10x6, true) => Format::Astc10x6RgbaUnormSrgb,
+                (Block::B10x8, false) => Format::Astc10x8RgbaUnorm,
+                (Block::B10x8, true) => Format::Astc10x8RgbaUnormSrgb,
+                (Block::B10x10, false) => Format::Astc10x10RgbaUnorm,
+                (Block::B10x10, true) => Format::Astc10x10RgbaUnormSrgb,
+                (Block::B12x10, false) => Format::Astc12x10RgbaUnorm,
+                (Block::B12x10, true) => Format::Astc12x10RgbaUnormSrgb,
+                (Block::B12x12, false) => Format::Astc12x12RgbaUnorm,
+                (Block::B12x12, true) => Format::Astc12x12RgbaUnormSrgb,
+            },
         }
     }
 
@@ -389,6 +786,16 @@ impl<'a> ModelParser<'a> {
             (Texel::Unorm, Count::One) => Format::R8Unorm,
             (Texel::Unorm, Count::Two) => Format::Rg8Unorm,
             (Texel::Unorm, Count::Four) => Format::Rgba8Unorm,
+
+            // 16-bit snorm
+            (Texel::Snorm16, Count::One) => Format::R16Snorm,
+            (Texel::Snorm16, Count::Two) => Format::Rg16Snorm,
+            (Texel::Snorm16, Count::Four) => Format::Rgba16Snorm,
+
+            // 16-bit unorm
+            (Texel::Unorm16, Count::One) => Format::R16Unorm,
+            (Texel::Unorm16, Count::Two) => Format::Rg16Unorm,
+            (Texel::Unorm16, Count::Four) => Format::Rgba16Unorm,
         }
     }
 }
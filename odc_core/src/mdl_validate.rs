@@ -0,0 +1,1665 @@
+use crate::mdl;
+use crate::mdl_parse::ModelParser;
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ValidationError {
+    PassPipelineNotFound {
+        pass: mdl::PassId,
+        pipeline: mdl::PipelineId,
+    },
+    PassTextureNotFound {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    ColorAttachmentIsDepth {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    DepthAttachmentIsNotDepth {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    AttachmentNotRenderable {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    AttachmentNotD2 {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    ResolveTargetMultisampled {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    ResolveTargetFormatMismatch {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    PassSampleCountMismatch {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    PassViewRangeInvalid {
+        pass: mdl::PassId,
+        texture: mdl::TextureId,
+    },
+    PipelineNotUsed {
+        pipeline: mdl::PipelineId,
+    },
+    PipelineBindGroupNotFound {
+        pipeline: mdl::PipelineId,
+        bind_group: mdl::BindGroupId,
+    },
+    PipelineBufferNotFound {
+        pipeline: mdl::PipelineId,
+        buffer: mdl::BufferId,
+    },
+    PipelineLocationOverlap {
+        pipeline: mdl::PipelineId,
+        location: u32,
+    },
+    PipelineTargetsMismatch {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+        targets: usize,
+        attachments: usize,
+    },
+    PipelineColorFormatsMismatch {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+    },
+    PipelineDepthWithoutAttachment {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+    },
+    PipelineAttachmentWithoutDepth {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+    },
+    PipelineDepthFormatMismatch {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+    },
+    PipelineStencilWithoutStencilFormat {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+    },
+    PipelineSampleCountMismatch {
+        pipeline: mdl::PipelineId,
+        pass: mdl::PassId,
+    },
+    PipelineAlphaToCoverageWithoutMultisampling {
+        pipeline: mdl::PipelineId,
+    },
+    PipelineStripIndexFormat {
+        pipeline: mdl::PipelineId,
+    },
+    PipelineStripIndexFormatMismatch {
+        pipeline: mdl::PipelineId,
+    },
+    PipelinePushConstantRangeInvalid {
+        pipeline: mdl::PipelineId,
+        range: std::ops::Range<u32>,
+    },
+    PipelinePushConstantStagesOverlap {
+        pipeline: mdl::PipelineId,
+    },
+    ComputePipelineBindGroupNotFound {
+        pipeline: mdl::ComputePipelineId,
+        bind_group: mdl::BindGroupId,
+    },
+    BindGroupTextureNotFound {
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+    },
+    BindGroupSamplerNotFound {
+        bind_group: mdl::BindGroupId,
+        sampler: mdl::SamplerId,
+    },
+    BindGroupBufferNotFound {
+        bind_group: mdl::BindGroupId,
+        buffer: mdl::BufferId,
+    },
+    BindGroupStorageTextureNotFound {
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+    },
+    BindGroupBindingOverlap {
+        bind_group: mdl::BindGroupId,
+        binding: u32,
+    },
+    BufferUsageMissing {
+        buffer: mdl::BufferId,
+        usage: &'static str,
+    },
+    BindGroupBufferOutOfBounds {
+        bind_group: mdl::BindGroupId,
+        buffer: mdl::BufferId,
+        end: u64,
+        buffer_size: u64,
+    },
+    BindGroupBufferRangeOverflow {
+        bind_group: mdl::BindGroupId,
+        buffer: mdl::BufferId,
+    },
+    BindGroupDynamicBufferWithoutSize {
+        bind_group: mdl::BindGroupId,
+        buffer: mdl::BufferId,
+    },
+    BindGroupTextureDimensionMismatch {
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+        dimension: mdl::TextureViewDimension,
+    },
+    BindGroupViewLayerCountInvalid {
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+        dimension: mdl::TextureViewDimension,
+    },
+    BindGroupViewRangeInvalid {
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+    },
+    BindGroupStorageTextureUnsupported {
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+    },
+    BindGroupStorageViewMipLevels {
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+    },
+    TextureD1SizeInvalid {
+        texture: mdl::TextureId,
+    },
+    TextureMultisampledNotD2 {
+        texture: mdl::TextureId,
+    },
+    TextureMultisampledMipmaps {
+        texture: mdl::TextureId,
+    },
+    TextureNotBlockAligned {
+        texture: mdl::TextureId,
+    },
+    TextureAutoMipmapsUnsupported {
+        texture: mdl::TextureId,
+    },
+    SamplerLinearNonFilter {
+        sampler: mdl::SamplerId,
+    },
+    SamplerLodClampInvalid {
+        sampler: mdl::SamplerId,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PassPipelineNotFound { pass, pipeline } => {
+                write!(f, "pass {}: pipeline {} not found", pass.0, pipeline.0)
+            }
+            Self::PassTextureNotFound { pass, texture } => {
+                write!(
+                    f,
+                    "pass {}: attachment texture {} not found",
+                    pass.0, texture.0
+                )
+            }
+            Self::ColorAttachmentIsDepth { pass, texture } => write!(
+                f,
+                "pass {}: depth texture {} used as color attachment",
+                pass.0, texture.0
+            ),
+            Self::DepthAttachmentIsNotDepth { pass, texture } => write!(
+                f,
+                "pass {}: non-depth texture {} used as depth attachment",
+                pass.0, texture.0
+            ),
+            Self::AttachmentNotRenderable { pass, texture } => write!(
+                f,
+                "pass {}: texture {} with non-renderable format used as attachment",
+                pass.0, texture.0
+            ),
+            Self::AttachmentNotD2 { pass, texture } => write!(
+                f,
+                "pass {}: non-2D texture {} used as attachment",
+                pass.0, texture.0
+            ),
+            Self::ResolveTargetMultisampled { pass, texture } => write!(
+                f,
+                "pass {}: multisampled texture {} used as resolve target",
+                pass.0, texture.0
+            ),
+            Self::ResolveTargetFormatMismatch { pass, texture } => write!(
+                f,
+                "pass {}: resolve target {} format differs from its attachment format",
+                pass.0, texture.0
+            ),
+            Self::PassSampleCountMismatch { pass, texture } => write!(
+                f,
+                "pass {}: sample count of attachment {} differs from other attachments",
+                pass.0, texture.0
+            ),
+            Self::PassViewRangeInvalid { pass, texture } => write!(
+                f,
+                "pass {}: view of attachment {} must select single mip level and layer of all aspects",
+                pass.0, texture.0
+            ),
+            Self::PipelineNotUsed { pipeline } => {
+                write!(f, "pipeline {}: not used in any pass", pipeline.0)
+            }
+            Self::PipelineBindGroupNotFound {
+                pipeline,
+                bind_group,
+            } => write!(
+                f,
+                "pipeline {}: bind group {} not found",
+                pipeline.0, bind_group.0
+            ),
+            Self::PipelineBufferNotFound { pipeline, buffer } => {
+                write!(f, "pipeline {}: buffer {} not found", pipeline.0, buffer.0)
+            }
+            Self::PipelineLocationOverlap { pipeline, location } => write!(
+                f,
+                "pipeline {}: input location {} used more than once",
+                pipeline.0, location
+            ),
+            Self::PipelineTargetsMismatch {
+                pipeline,
+                pass,
+                targets,
+                attachments,
+            } => write!(
+                f,
+                "pipeline {}: {} color targets for {} color attachments of pass {}",
+                pipeline.0, targets, attachments, pass.0
+            ),
+            Self::PipelineColorFormatsMismatch { pipeline, pass } => write!(
+                f,
+                "pipeline {}: color attachment formats of pass {} differ from other passes",
+                pipeline.0, pass.0
+            ),
+            Self::PipelineDepthWithoutAttachment { pipeline, pass } => write!(
+                f,
+                "pipeline {}: depth test enabled, but pass {} has no depth attachment",
+                pipeline.0, pass.0
+            ),
+            Self::PipelineAttachmentWithoutDepth { pipeline, pass } => write!(
+                f,
+                "pipeline {}: pass {} has depth attachment, but depth test is disabled",
+                pipeline.0, pass.0
+            ),
+            Self::PipelineDepthFormatMismatch { pipeline, pass } => write!(
+                f,
+                "pipeline {}: depth attachment format of pass {} differs from other passes",
+                pipeline.0, pass.0
+            ),
+            Self::PipelineStencilWithoutStencilFormat { pipeline, pass } => write!(
+                f,
+                "pipeline {}: stencil test enabled, but depth attachment of pass {} has no stencil",
+                pipeline.0, pass.0
+            ),
+            Self::PipelineSampleCountMismatch { pipeline, pass } => write!(
+                f,
+                "pipeline {}: sample count differs from attachments of pass {}",
+                pipeline.0, pass.0
+            ),
+            Self::PipelineAlphaToCoverageWithoutMultisampling { pipeline } => write!(
+                f,
+                "pipeline {}: alpha to coverage enabled without multisampling",
+                pipeline.0
+            ),
+            Self::PipelineStripIndexFormat { pipeline } => write!(
+                f,
+                "pipeline {}: strip index format set for non-strip topology",
+                pipeline.0
+            ),
+            Self::PipelineStripIndexFormatMismatch { pipeline } => write!(
+                f,
+                "pipeline {}: strip index format differs from index format",
+                pipeline.0
+            ),
+            Self::PipelinePushConstantRangeInvalid { pipeline, range } => write!(
+                f,
+                "pipeline {}: push constant range {}..{} is empty or not aligned to 4 bytes",
+                pipeline.0, range.start, range.end
+            ),
+            Self::PipelinePushConstantStagesOverlap { pipeline } => write!(
+                f,
+                "pipeline {}: shader stage is used by several push constant ranges",
+                pipeline.0
+            ),
+            Self::ComputePipelineBindGroupNotFound {
+                pipeline,
+                bind_group,
+            } => write!(
+                f,
+                "compute pipeline {}: bind group {} not found",
+                pipeline.0, bind_group.0
+            ),
+            Self::BindGroupTextureNotFound {
+                bind_group,
+                texture,
+            } => write!(
+                f,
+                "bind group {}: texture {} not found",
+                bind_group.0, texture.0
+            ),
+            Self::BindGroupSamplerNotFound {
+                bind_group,
+                sampler,
+            } => write!(
+                f,
+                "bind group {}: sampler {} not found",
+                bind_group.0, sampler.0
+            ),
+            Self::BindGroupBufferNotFound { bind_group, buffer } => write!(
+                f,
+                "bind group {}: buffer {} not found",
+                bind_group.0, buffer.0
+            ),
+            Self::BindGroupStorageTextureNotFound {
+                bind_group,
+                texture,
+            } => write!(
+                f,
+                "bind group {}: storage texture {} not found",
+                bind_group.0, texture.0
+            ),
+            Self::BindGroupBindingOverlap {
+                bind_group,
+                binding,
+            } => write!(
+                f,
+                "bind group {}: binding {} used more than once",
+                bind_group.0, binding
+            ),
+            Self::BufferUsageMissing { buffer, usage } => {
+                write!(
+                    f,
+                    "buffer {}: used as {}, but has no such usage",
+                    buffer.0, usage
+                )
+            }
+            Self::BindGroupBufferOutOfBounds {
+                bind_group,
+                buffer,
+                end,
+                buffer_size,
+            } => write!(
+                f,
+                "bind group {}: binding ends at {}, but buffer {} size is {}",
+                bind_group.0, end, buffer.0, buffer_size
+            ),
+            Self::BindGroupBufferRangeOverflow { bind_group, buffer } => write!(
+                f,
+                "bind group {}: binding range of buffer {} overflows",
+                bind_group.0, buffer.0
+            ),
+            Self::BindGroupDynamicBufferWithoutSize { bind_group, buffer } => write!(
+                f,
+                "bind group {}: dynamic offset binding of buffer {} has no size",
+                bind_group.0, buffer.0
+            ),
+            Self::BindGroupTextureDimensionMismatch {
+                bind_group,
+                texture,
+                dimension,
+            } => write!(
+                f,
+                "bind group {}: texture {} can't be viewed as {:?}",
+                bind_group.0, texture.0, dimension
+            ),
+            Self::BindGroupViewLayerCountInvalid {
+                bind_group,
+                texture,
+                dimension,
+            } => write!(
+                f,
+                "bind group {}: view of texture {} has wrong number of layers for {:?}",
+                bind_group.0, texture.0, dimension
+            ),
+            Self::BindGroupViewRangeInvalid {
+                bind_group,
+                texture,
+            } => write!(
+                f,
+                "bind group {}: view range of texture {} is out of bounds or has missing aspect",
+                bind_group.0, texture.0
+            ),
+            Self::BindGroupStorageTextureUnsupported {
+                bind_group,
+                texture,
+            } => write!(
+                f,
+                "bind group {}: texture {} can't be storage, it must be single sampled with storage format",
+                bind_group.0, texture.0
+            ),
+            Self::BindGroupStorageViewMipLevels {
+                bind_group,
+                texture,
+            } => write!(
+                f,
+                "bind group {}: storage view of texture {} must select single mip level",
+                bind_group.0, texture.0
+            ),
+            Self::TextureD1SizeInvalid { texture } => write!(
+                f,
+                "texture {}: 1D texture must have height and depth of 1",
+                texture.0
+            ),
+            Self::TextureMultisampledNotD2 { texture } => write!(
+                f,
+                "texture {}: only 2D texture can be multisampled",
+                texture.0
+            ),
+            Self::TextureMultisampledMipmaps { texture } => write!(
+                f,
+                "texture {}: multisampled texture must have single mip level",
+                texture.0
+            ),
+            Self::TextureNotBlockAligned { texture } => write!(
+                f,
+                "texture {}: size is not multiple of compression block size",
+                texture.0
+            ),
+            Self::TextureAutoMipmapsUnsupported { texture } => write!(
+                f,
+                "texture {}: auto mipmaps require writable texture with filterable and renderable format",
+                texture.0
+            ),
+            Self::SamplerLinearNonFilter { sampler } => write!(
+                f,
+                "sampler {}: non-filtering sampler can't use linear filters",
+                sampler.0
+            ),
+            Self::SamplerLodClampInvalid { sampler } => write!(
+                f,
+                "sampler {}: lod clamp must be non-negative range",
+                sampler.0
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+pub struct ModelValidator<'a> {
+    model: &'a mdl::RenderModel,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> ModelValidator<'a> {
+    pub fn new(model: &'a mdl::RenderModel) -> Self {
+        Self {
+            model,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn validate(mut self) -> Vec<ValidationError> {
+        for (i, pass) in self.model.passes.iter().enumerate() {
+            self.validate_pass(mdl::PassId(i), pass);
+        }
+
+        for (i, pipeline) in self.model.pipelines.iter().enumerate() {
+            self.validate_pipeline(mdl::PipelineId(i), pipeline);
+        }
+
+        for (i, pipeline) in self.model.compute_pipelines.iter().enumerate() {
+            self.validate_compute_pipeline(mdl::ComputePipelineId(i), pipeline);
+        }
+
+        for (i, bind_group) in self.model.bind_groups.iter().enumerate() {
+            self.validate_bind_group(mdl::BindGroupId(i), bind_group);
+        }
+
+        for (i, texture) in self.model.textures.iter().enumerate() {
+            self.validate_texture(mdl::TextureId(i), texture);
+        }
+
+        for (i, sampler) in self.model.samplers.iter().enumerate() {
+            self.validate_sampler(mdl::SamplerId(i), sampler);
+        }
+
+        self.errors
+    }
+
+    fn validate_sampler(&mut self, index: mdl::SamplerId, sampler: &mdl::Sampler) {
+        if let Some(filters) = sampler.filters {
+            let filters = [filters.min, filters.mag, filters.mipmap];
+            let has_linear = filters.contains(&mdl::Filter::Linear);
+            if sampler.typ == mdl::SamplerType::NonFilter && has_linear {
+                self.errors
+                    .push(ValidationError::SamplerLinearNonFilter { sampler: index });
+            }
+        }
+
+        let lod = sampler.lod_clamp;
+        if !(lod.min >= 0.0 && lod.min <= lod.max) {
+            self.errors
+                .push(ValidationError::SamplerLodClampInvalid { sampler: index });
+        }
+    }
+
+    fn validate_texture(&mut self, index: mdl::TextureId, texture: &mdl::Texture) {
+        if let mdl::TextureType::Compressed(format) = texture.typ {
+            let (width, height) = format.block_size();
+            if texture.size.width % width != 0 || texture.size.height % height != 0 {
+                self.errors
+                    .push(ValidationError::TextureNotBlockAligned { texture: index });
+            }
+        }
+
+        let size = texture.size;
+        if texture.dimension == mdl::TextureDimension::D1
+            && (size.height != 1 || size.depth_or_array_layers != 1)
+        {
+            self.errors
+                .push(ValidationError::TextureD1SizeInvalid { texture: index });
+        }
+
+        let multisampled = texture.sample_count != mdl::SampleCount::One;
+        if multisampled && texture.dimension != mdl::TextureDimension::D2 {
+            self.errors
+                .push(ValidationError::TextureMultisampledNotD2 { texture: index });
+        }
+
+        if multisampled && texture.mip_levels > 1 {
+            self.errors
+                .push(ValidationError::TextureMultisampledMipmaps { texture: index });
+        }
+
+        if texture.auto_mipmaps && !(texture.writable && ModelParser::supports_mipmaps(texture)) {
+            self.errors
+                .push(ValidationError::TextureAutoMipmapsUnsupported { texture: index });
+        }
+    }
+
+    fn validate_pass(&mut self, index: mdl::PassId, pass: &mdl::Pass) {
+        for &pipeline in pass.pipelines.iter() {
+            if pipeline.0 >= self.model.pipelines.len() {
+                self.errors.push(ValidationError::PassPipelineNotFound {
+                    pass: index,
+                    pipeline,
+                });
+            }
+        }
+
+        for attachment in pass.color_attachments.iter() {
+            let textures = Some(attachment.texture)
+                .into_iter()
+                .chain(attachment.resolve);
+            for texture in textures {
+                match self.model.textures.get(texture.0) {
+                    None => self.errors.push(ValidationError::PassTextureNotFound {
+                        pass: index,
+                        texture,
+                    }),
+                    Some(info) if info.typ.is_depth() => {
+                        self.errors.push(ValidationError::ColorAttachmentIsDepth {
+                            pass: index,
+                            texture,
+                        })
+                    }
+                    Some(info) if !Self::is_renderable(info) => {
+                        self.errors.push(ValidationError::AttachmentNotRenderable {
+                            pass: index,
+                            texture,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for attachment in pass.color_attachments.iter() {
+            let textures = &self.model.textures;
+            let resolve = attachment
+                .resolve
+                .and_then(|id| Some((id, textures.get(id.0)?)));
+            let source = textures.get(attachment.texture.0);
+            if let (Some((texture, target)), Some(source)) = (resolve, source) {
+                if target.sample_count != mdl::SampleCount::One {
+                    self.errors
+                        .push(ValidationError::ResolveTargetMultisampled {
+                            pass: index,
+                            texture,
+                        });
+                }
+                let target_format = ModelParser::parse_texture_format(target.typ);
+                if target_format != ModelParser::parse_texture_format(source.typ) {
+                    self.errors
+                        .push(ValidationError::ResolveTargetFormatMismatch {
+                            pass: index,
+                            texture,
+                        });
+                }
+            }
+        }
+
+        if let Some(attachment) = &pass.depth_attachment {
+            let texture = attachment.texture;
+            match self.model.textures.get(texture.0) {
+                None => self.errors.push(ValidationError::PassTextureNotFound {
+                    pass: index,
+                    texture,
+                }),
+                Some(info) if !info.typ.is_depth() => {
+                    self.errors
+                        .push(ValidationError::DepthAttachmentIsNotDepth {
+                            pass: index,
+                            texture,
+                        })
+                }
+                _ => {}
+            }
+        }
+
+        let resolve_iter = pass.color_attachments.iter().flat_map(|att| att.resolve);
+        let color_iter = pass.color_attachments.iter().map(|att| att.texture);
+        let depth_iter = pass.depth_attachment.iter().map(|att| att.texture);
+        for texture in color_iter.chain(resolve_iter).chain(depth_iter) {
+            if let Some(info) = self.model.textures.get(texture.0) {
+                if info.dimension != mdl::TextureDimension::D2 {
+                    self.errors.push(ValidationError::AttachmentNotD2 {
+                        pass: index,
+                        texture,
+                    });
+                }
+            }
+        }
+
+        let sample_count = self.model.pass_sample_count(index);
+        let color_iter = pass.color_attachments.iter().map(|att| att.texture);
+        let depth_iter = pass.depth_attachment.iter().map(|att| att.texture);
+        for texture in color_iter.chain(depth_iter) {
+            if let Some(info) = self.model.textures.get(texture.0) {
+                if Some(info.sample_count) != sample_count {
+                    self.errors.push(ValidationError::PassSampleCountMismatch {
+                        pass: index,
+                        texture,
+                    });
+                }
+            }
+        }
+
+        let color_iter = pass
+            .color_attachments
+            .iter()
+            .map(|att| (att.texture, att.range));
+        let depth_iter = pass
+            .depth_attachment
+            .iter()
+            .map(|att| (att.texture, att.range));
+        for (texture, range) in color_iter.chain(depth_iter) {
+            if let Some(info) = self.model.textures.get(texture.0) {
+                let single = range
+                    .mip_levels(info.mip_levels)
+                    .is_some_and(|l| l.len() == 1)
+                    && range
+                        .array_layers(info.array_layers())
+                        .is_some_and(|l| l.len() == 1)
+                    && range.aspect == mdl::TextureAspect::All;
+                if !single || !Self::view_range_fits(info, range) {
+                    self.errors.push(ValidationError::PassViewRangeInvalid {
+                        pass: index,
+                        texture,
+                    });
+                }
+            }
+        }
+    }
+
+    fn is_renderable(texture: &mdl::Texture) -> bool {
+        let format = ModelParser::parse_texture_format(texture.typ).describe();
+        let usages = format.guaranteed_format_features.allowed_usages;
+        usages.contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+    }
+
+    fn validate_pipeline(&mut self, index: mdl::PipelineId, pipeline: &mdl::RenderPipeline) {
+        for &bind_group in pipeline.bind_groups.iter() {
+            if bind_group.0 >= self.model.bind_groups.len() {
+                self.errors
+                    .push(ValidationError::PipelineBindGroupNotFound {
+                        pipeline: index,
+                        bind_group,
+                    });
+            }
+        }
+
+        let mut used = false;
+        let mut color_formats = None;
+        let mut depth_format = None;
+        for (pass_index, pass) in self.model.passes.iter().enumerate() {
+            if !pass.pipelines.contains(&index) {
+                continue;
+            }
+            used = true;
+
+            let sample_count = self.model.pass_sample_count(mdl::PassId(pass_index));
+            if sample_count.is_some_and(|count| count != pipeline.multisample.count) {
+                self.errors
+                    .push(ValidationError::PipelineSampleCountMismatch {
+                        pipeline: index,
+                        pass: mdl::PassId(pass_index),
+                    });
+            }
+
+            if pipeline.targets.len() < pass.color_attachments.len() {
+                self.errors.push(ValidationError::PipelineTargetsMismatch {
+                    pipeline: index,
+                    pass: mdl::PassId(pass_index),
+                    targets: pipeline.targets.len(),
+                    attachments: pass.color_attachments.len(),
+                });
+            }
+
+            // Pipeline targets are created for formats of the first pass.
+            let formats: Option<Vec<_>> = pass
+                .color_attachments
+                .iter()
+                .map(|attachment| self.model.textures.get(attachment.texture.0))
+                .map(|texture| texture.map(|t| ModelParser::parse_texture_format(t.typ)))
+                .collect();
+            if let Some(formats) = formats {
+                if *color_formats.get_or_insert_with(|| formats.clone()) != formats {
+                    self.errors
+                        .push(ValidationError::PipelineColorFormatsMismatch {
+                            pipeline: index,
+                            pass: mdl::PassId(pass_index),
+                        });
+                }
+            }
+
+            if pipeline.depth.is_none() && pass.depth_attachment.is_some() {
+                self.errors
+                    .push(ValidationError::PipelineAttachmentWithoutDepth {
+                        pipeline: index,
+                        pass: mdl::PassId(pass_index),
+                    });
+            }
+
+            if let Some(ops) = &pipeline.depth {
+                let texture = pass
+                    .depth_attachment
+                    .as_ref()
+                    .map(|attachment| self.model.textures.get(attachment.texture.0));
+                match texture {
+                    None => self
+                        .errors
+                        .push(ValidationError::PipelineDepthWithoutAttachment {
+                            pipeline: index,
+                            pass: mdl::PassId(pass_index),
+                        }),
+                    Some(Some(mdl::Texture {
+                        typ: mdl::TextureType::Depth(format),
+                        ..
+                    })) => {
+                        let format = *format;
+                        if *depth_format.get_or_insert(format) != format {
+                            self.errors
+                                .push(ValidationError::PipelineDepthFormatMismatch {
+                                    pipeline: index,
+                                    pass: mdl::PassId(pass_index),
+                                });
+                        }
+
+                        if ops.stencil.is_enabled() && !format.has_stencil() {
+                            self.errors.push(
+                                ValidationError::PipelineStencilWithoutStencilFormat {
+                                    pipeline: index,
+                                    pass: mdl::PassId(pass_index),
+                                },
+                            );
+                        }
+                    }
+                    // Missing or non-depth textures are reported by pass validation.
+                    _ => {}
+                }
+            }
+        }
+
+        if !used {
+            self.errors
+                .push(ValidationError::PipelineNotUsed { pipeline: index });
+        }
+
+        let multisample = &pipeline.multisample;
+        if multisample.alpha_to_coverage && multisample.count == mdl::SampleCount::One {
+            self.errors.push(
+                ValidationError::PipelineAlphaToCoverageWithoutMultisampling { pipeline: index },
+            );
+        }
+
+        let mut push_constant_stages = wgpu::ShaderStages::NONE;
+        for push_constants in pipeline.push_constants.iter() {
+            let stages = ModelParser::parse_visibility(push_constants.stages);
+            if push_constant_stages.intersects(stages) {
+                self.errors
+                    .push(ValidationError::PipelinePushConstantStagesOverlap { pipeline: index });
+            }
+            push_constant_stages |= stages;
+
+            let range = &push_constants.range;
+            if range.is_empty() || range.start % 4 != 0 || range.end % 4 != 0 {
+                self.errors
+                    .push(ValidationError::PipelinePushConstantRangeInvalid {
+                        pipeline: index,
+                        range: range.clone(),
+                    });
+            }
+        }
+
+        let primitive = &pipeline.primitive;
+        if primitive.strip_index_format.is_some() && !primitive.topology.is_strip() {
+            self.errors
+                .push(ValidationError::PipelineStripIndexFormat { pipeline: index });
+        }
+
+        let input = &pipeline.input;
+        let strip_index_format = primitive.strip_index_format;
+        if input.index.is_some() && strip_index_format.is_some_and(|f| f != input.index_format) {
+            self.errors
+                .push(ValidationError::PipelineStripIndexFormatMismatch { pipeline: index });
+        }
+
+        let mut locations = HashSet::new();
+        let attributes = input.streams.iter().flat_map(|s| s.attributes.iter());
+        for attribute in attributes {
+            if !locations.insert(attribute.location) {
+                self.errors.push(ValidationError::PipelineLocationOverlap {
+                    pipeline: index,
+                    location: attribute.location,
+                });
+            }
+        }
+
+        if let Some(buffer) = input.index {
+            if self.check_buffer(buffer, "index", |u| u.index).is_none() {
+                self.errors.push(ValidationError::PipelineBufferNotFound {
+                    pipeline: index,
+                    buffer,
+                });
+            }
+        }
+
+        for stream in input.streams.iter() {
+            let buffer = stream.buffer;
+            if self.check_buffer(buffer, "vertex", |u| u.vertex).is_none() {
+                self.errors.push(ValidationError::PipelineBufferNotFound {
+                    pipeline: index,
+                    buffer,
+                });
+            }
+        }
+    }
+
+    /// Checks that existing buffer has `usage`. Returns `None` if buffer not found.
+    fn check_buffer(
+        &mut self,
+        buffer: mdl::BufferId,
+        usage: &'static str,
+        has_usage: fn(&mdl::BufferUsage) -> bool,
+    ) -> Option<&'a mdl::Buffer> {
+        let buffer_model = self.model.buffers.get(buffer.0)?;
+        if !has_usage(&buffer_model.usage) {
+            self.errors
+                .push(ValidationError::BufferUsageMissing { buffer, usage });
+        }
+        Some(buffer_model)
+    }
+
+    fn check_binding_bounds(
+        &mut self,
+        bind_group: mdl::BindGroupId,
+        buffer: mdl::BufferId,
+        buffer_model: &mdl::Buffer,
+        offset: u64,
+        size: u64,
+    ) {
+        let end = match offset.checked_add(size) {
+            Some(end) => end,
+            None => {
+                self.errors
+                    .push(ValidationError::BindGroupBufferRangeOverflow { bind_group, buffer });
+                return;
+            }
+        };
+
+        if end > buffer_model.size {
+            self.errors
+                .push(ValidationError::BindGroupBufferOutOfBounds {
+                    bind_group,
+                    buffer,
+                    end,
+                    buffer_size: buffer_model.size,
+                });
+        }
+    }
+
+    fn validate_compute_pipeline(
+        &mut self,
+        index: mdl::ComputePipelineId,
+        pipeline: &mdl::ComputePipeline,
+    ) {
+        for &bind_group in pipeline.bind_groups.iter() {
+            if bind_group.0 >= self.model.bind_groups.len() {
+                self.errors
+                    .push(ValidationError::ComputePipelineBindGroupNotFound {
+                        pipeline: index,
+                        bind_group,
+                    });
+            }
+        }
+    }
+
+    fn validate_bind_group(&mut self, index: mdl::BindGroupId, bind_group: &mdl::BindGroup) {
+        let mut bindings = HashSet::new();
+        let uniform_indices = bind_group.uniforms.iter().map(|b| b.index);
+        let texture_indices = bind_group.textures.iter().map(|b| b.index);
+        let sampler_indices = bind_group.samplers.iter().map(|b| b.index);
+        let storage_buffer_indices = bind_group.storage_buffers.iter().map(|b| b.index);
+        let storage_texture_indices = bind_group.storage_textures.iter().map(|b| b.index);
+        let indices = uniform_indices
+            .chain(texture_indices)
+            .chain(sampler_indices)
+            .chain(storage_buffer_indices)
+            .chain(storage_texture_indices);
+        for binding in indices {
+            if !bindings.insert(binding) {
+                self.errors.push(ValidationError::BindGroupBindingOverlap {
+                    bind_group: index,
+                    binding,
+                });
+            }
+        }
+
+        for uniform in bind_group.uniforms.iter() {
+            let buffer = uniform.info.buffer;
+            match self.check_buffer(buffer, "uniform", |u| u.uniform) {
+                None => self.errors.push(ValidationError::BindGroupBufferNotFound {
+                    bind_group: index,
+                    buffer,
+                }),
+                Some(buffer_model) => {
+                    let (offset, size) = (uniform.info.offset, uniform.info.size);
+                    self.check_binding_bounds(index, buffer, buffer_model, offset, size);
+                }
+            }
+        }
+
+        for binding in bind_group.textures.iter() {
+            let texture = binding.info.texture;
+            if texture.0 >= self.model.textures.len() {
+                self.errors.push(ValidationError::BindGroupTextureNotFound {
+                    bind_group: index,
+                    texture,
+                });
+            } else {
+                self.check_view_dimension(index, texture, binding.info.dimension);
+                self.check_view_layers(index, texture, binding.info.dimension, binding.info.range);
+                self.check_view_range(index, texture, binding.info.range);
+            }
+        }
+
+        for binding in bind_group.samplers.iter() {
+            let sampler = binding.info.sampler;
+            if sampler.0 >= self.model.samplers.len() {
+                self.errors.push(ValidationError::BindGroupSamplerNotFound {
+                    bind_group: index,
+                    sampler,
+                });
+            }
+        }
+
+        for binding in bind_group.storage_buffers.iter() {
+            let buffer = binding.info.buffer;
+            match self.check_buffer(buffer, "storage", |u| u.storage) {
+                None => self.errors.push(ValidationError::BindGroupBufferNotFound {
+                    bind_group: index,
+                    buffer,
+                }),
+                Some(buffer_model) => {
+                    if binding.info.dynamic_offset && binding.info.size.is_none() {
+                        self.errors
+                            .push(ValidationError::BindGroupDynamicBufferWithoutSize {
+                                bind_group: index,
+                                buffer,
+                            });
+                    }
+
+                    let size = binding.info.size.unwrap_or_default();
+                    let offset = binding.info.offset;
+                    self.check_binding_bounds(index, buffer, buffer_model, offset, size);
+                }
+            }
+        }
+
+        for binding in bind_group.storage_textures.iter() {
+            let texture = binding.info.texture;
+            if texture.0 >= self.model.textures.len() {
+                self.errors
+                    .push(ValidationError::BindGroupStorageTextureNotFound {
+                        bind_group: index,
+                        texture,
+                    });
+            } else {
+                self.check_view_dimension(index, texture, binding.info.dimension);
+                self.check_view_layers(index, texture, binding.info.dimension, binding.info.range);
+                self.check_view_range(index, texture, binding.info.range);
+
+                let info = &self.model.textures[texture.0];
+                let format = ModelParser::parse_texture_format(info.typ).describe();
+                let storage_format = format
+                    .guaranteed_format_features
+                    .allowed_usages
+                    .contains(wgpu::TextureUsages::STORAGE_BINDING);
+                if !storage_format || info.sample_count != mdl::SampleCount::One {
+                    self.errors
+                        .push(ValidationError::BindGroupStorageTextureUnsupported {
+                            bind_group: index,
+                            texture,
+                        });
+                }
+
+                let levels = binding.info.range.mip_levels(info.mip_levels);
+                if levels.is_some_and(|l| l.len() != 1) {
+                    self.errors
+                        .push(ValidationError::BindGroupStorageViewMipLevels {
+                            bind_group: index,
+                            texture,
+                        });
+                }
+            }
+        }
+    }
+
+    fn check_view_range(
+        &mut self,
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+        range: mdl::ViewRange,
+    ) {
+        if !Self::view_range_fits(&self.model.textures[texture.0], range) {
+            self.errors
+                .push(ValidationError::BindGroupViewRangeInvalid {
+                    bind_group,
+                    texture,
+                });
+        }
+    }
+
+    fn view_range_fits(texture: &mdl::Texture, range: mdl::ViewRange) -> bool {
+        let levels = range.mip_levels(texture.mip_levels);
+        let layers = range.array_layers(texture.array_layers());
+        let (Some(levels), Some(layers)) = (levels, layers) else {
+            return false;
+        };
+        let aspect_fits = match range.aspect {
+            mdl::TextureAspect::All => true,
+            mdl::TextureAspect::DepthOnly => texture.typ.is_depth(),
+            mdl::TextureAspect::StencilOnly => {
+                matches!(texture.typ, mdl::TextureType::Depth(format) if format.has_stencil())
+            }
+        };
+        !levels.is_empty()
+            && levels.end <= texture.mip_levels
+            && !layers.is_empty()
+            && layers.end <= texture.array_layers()
+            && aspect_fits
+    }
+
+    /// Cube views have 6 layers per cube, other non-array views have single layer.
+    fn check_view_layers(
+        &mut self,
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+        dimension: mdl::TextureViewDimension,
+        range: mdl::ViewRange,
+    ) {
+        let info = &self.model.textures[texture.0];
+        // Overflowing ranges are reported by `check_view_range`.
+        let layers = match range.array_layers(info.array_layers()) {
+            Some(layers) => layers.len(),
+            None => return,
+        };
+        let valid = match dimension {
+            mdl::TextureViewDimension::D2Array => true,
+            mdl::TextureViewDimension::Cube => layers == 6,
+            mdl::TextureViewDimension::CubeArray => layers % 6 == 0,
+            _ => layers == 1,
+        };
+        if !valid {
+            self.errors
+                .push(ValidationError::BindGroupViewLayerCountInvalid {
+                    bind_group,
+                    texture,
+                    dimension,
+                });
+        }
+    }
+
+    fn check_view_dimension(
+        &mut self,
+        bind_group: mdl::BindGroupId,
+        texture: mdl::TextureId,
+        dimension: mdl::TextureViewDimension,
+    ) {
+        if !self.model.textures[texture.0]
+            .dimension
+            .is_compatible(dimension)
+        {
+            self.errors
+                .push(ValidationError::BindGroupTextureDimensionMismatch {
+                    bind_group,
+                    texture,
+                    dimension,
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::ValidationError;
+    use crate::mdl::*;
+
+    fn texture() -> Texture {
+        Texture {
+            name: None,
+            typ: TextureType::Color {
+                texel: TexelType::Unorm,
+                texel_count: TexelCount::Four,
+            },
+            dimension: TextureDimension::D2,
+            size: Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 1,
+            },
+            mip_levels: 1,
+            sample_count: SampleCount::One,
+            window_source: false,
+            writable: false,
+            auto_mipmaps: false,
+        }
+    }
+
+    fn pipeline() -> RenderPipeline {
+        let attributes = vec![InputAttribute {
+            item: InputItem::Float32x4,
+            offset: 0,
+            location: 0,
+        }];
+        RenderPipeline {
+            name: None,
+            input: PipelineInpit {
+                index: Some(BufferId(0)),
+                index_format: IndexFormat::Uint32,
+                streams: vec![VertexStream {
+                    buffer: BufferId(0),
+                    step_mode: StepMode::Vertex,
+                    attributes,
+                    stride: 16,
+                }],
+            },
+            bind_groups: vec![BindGroupId(0)],
+            push_constants: vec![],
+            shader: Shader {
+                path: "shader.wgsl".into(),
+                vs_main: "vs_main".into(),
+                fs_main: "fs_main".into(),
+            },
+            targets: vec![ColorTarget::default()],
+            depth: None,
+            multisample: Multisample::default(),
+            primitive: Primitive::default(),
+        }
+    }
+
+    fn attachment(texture: TextureId) -> Attachment {
+        Attachment {
+            texture,
+            resolve: None,
+            clear: None,
+            store: true,
+            range: ViewRange::default(),
+        }
+    }
+
+    pub(crate) fn model() -> RenderModel {
+        let geometry = Buffer {
+            name: None,
+            size: 1024,
+            usage: BufferUsage {
+                index: true,
+                vertex: true,
+                ..Default::default()
+            },
+        };
+        let uniform = Buffer {
+            name: None,
+            size: 256,
+            usage: BufferUsage {
+                uniform: true,
+                ..Default::default()
+            },
+        };
+
+        let bind_group = BindGroup {
+            uniforms: vec![Binding {
+                index: 0,
+                shader_stages: ShaderStages::Vertex,
+                info: UniformInfo {
+                    buffer: BufferId(1),
+                    size: 64,
+                    offset: 0,
+                    dynamic_offset: false,
+                },
+            }],
+            ..Default::default()
+        };
+
+        let pass = Pass {
+            name: None,
+            pipelines: vec![PipelineId(0)],
+            color_attachments: vec![attachment(TextureId(0))],
+            depth_attachment: None,
+        };
+
+        RenderModel {
+            passes: vec![pass],
+            pipelines: vec![pipeline()],
+            compute_pipelines: vec![],
+            bind_groups: vec![bind_group],
+            textures: vec![texture()],
+            samplers: vec![],
+            buffers: vec![geometry, uniform],
+        }
+    }
+
+    #[test]
+    fn valid_model() {
+        assert_eq!(model().validate(), vec![]);
+    }
+
+    #[test]
+    fn missing_references() {
+        let mut model = model();
+        model.passes[0].pipelines.push(PipelineId(5));
+        model.passes[0]
+            .color_attachments
+            .push(attachment(TextureId(7)));
+        model.pipelines[0].bind_groups.push(BindGroupId(3));
+
+        let errors = model.validate();
+        assert!(errors.contains(&ValidationError::PassPipelineNotFound {
+            pass: PassId(0),
+            pipeline: PipelineId(5),
+        }));
+        assert!(errors.contains(&ValidationError::PassTextureNotFound {
+            pass: PassId(0),
+            texture: TextureId(7),
+        }));
+        assert!(
+            errors.contains(&ValidationError::PipelineBindGroupNotFound {
+                pipeline: PipelineId(0),
+                bind_group: BindGroupId(3),
+            })
+        );
+    }
+
+    #[test]
+    fn unused_pipeline() {
+        let mut model = model();
+        model.pipelines.push(pipeline());
+        assert_eq!(
+            model.validate(),
+            vec![ValidationError::PipelineNotUsed {
+                pipeline: PipelineId(1)
+            }]
+        );
+    }
+
+    #[test]
+    fn location_overlap() {
+        let mut model = model();
+        let stream = &mut model.pipelines[0].input.streams[0];
+        stream.attributes.push(stream.attributes[0]);
+        assert_eq!(
+            model.validate(),
+            vec![ValidationError::PipelineLocationOverlap {
+                pipeline: PipelineId(0),
+                location: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn buffer_usage_missing() {
+        let mut model = model();
+        model.buffers[0].usage.index = false;
+        assert_eq!(
+            model.validate(),
+            vec![ValidationError::BufferUsageMissing {
+                buffer: BufferId(0),
+                usage: "index",
+            }]
+        );
+    }
+
+    #[test]
+    fn uniform_out_of_bounds() {
+        let mut model = model();
+        model.bind_groups[0].uniforms[0].info.offset = 256;
+        assert_eq!(
+            model.validate(),
+            vec![ValidationError::BindGroupBufferOutOfBounds {
+                bind_group: BindGroupId(0),
+                buffer: BufferId(1),
+                end: 320,
+                buffer_size: 256,
+            }]
+        );
+    }
+
+    #[test]
+    fn uniform_range_overflow() {
+        let mut model = model();
+        model.bind_groups[0].uniforms[0].info.offset = u64::MAX;
+        assert_eq!(
+            model.validate(),
+            vec![ValidationError::BindGroupBufferRangeOverflow {
+                bind_group: BindGroupId(0),
+                buffer: BufferId(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn view_layer_count() {
+        let mut model = model();
+        model.textures.push(Texture {
+            size: Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 6,
+            },
+            ..texture()
+        });
+        let binding = Binding {
+            index: 1,
+            shader_stages: ShaderStages::Fragment,
+            info: TextureInfo {
+                texture: TextureId(1),
+                dimension: TextureViewDimension::D2,
+                range: ViewRange::default(),
+            },
+        };
+        model.bind_groups[0].textures.push(binding);
+        let error = ValidationError::BindGroupViewLayerCountInvalid {
+            bind_group: BindGroupId(0),
+            texture: TextureId(1),
+            dimension: TextureViewDimension::D2,
+        };
+        assert!(model.validate().contains(&error));
+
+        let info = &mut model.bind_groups[0].textures[0].info;
+        info.dimension = TextureViewDimension::Cube;
+        assert!(model.validate().is_empty());
+    }
+
+    #[test]
+    fn view_range_overflow() {
+        let mut model = model();
+        model.bind_groups[0].textures.push(Binding {
+            index: 1,
+            shader_stages: ShaderStages::Fragment,
+            info: TextureInfo {
+                texture: TextureId(0),
+                dimension: TextureViewDimension::D2,
+                range: ViewRange {
+                    base_mip_level: 1,
+                    mip_level_count: Some(u32::MAX),
+                    ..Default::default()
+                },
+            },
+        });
+        assert!(model
+            .validate()
+            .contains(&ValidationError::BindGroupViewRangeInvalid {
+                bind_group: BindGroupId(0),
+                texture: TextureId(0),
+            }));
+    }
+
+    #[test]
+    fn binding_overlap() {
+        let mut model = model();
+        model.bind_groups[0].samplers.push(Binding {
+            index: 0,
+            shader_stages: ShaderStages::Fragment,
+            info: SamplerInfo {
+                sampler: SamplerId(0),
+            },
+        });
+        model.samplers.push(Sampler::default());
+        assert_eq!(
+            model.validate(),
+            vec![ValidationError::BindGroupBindingOverlap {
+                bind_group: BindGroupId(0),
+                binding: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn color_attachment_is_depth() {
+        let mut model = model();
+        model.textures[0].typ = TextureType::Depth(DepthFormat::Depth32Float);
+        assert!(model
+            .validate()
+            .contains(&ValidationError::ColorAttachmentIsDepth {
+                pass: PassId(0),
+                texture: TextureId(0),
+            }));
+    }
+
+    #[test]
+    fn resolve_target() {
+        let mut model = model();
+        model.textures[0].sample_count = SampleCount::Four;
+        model.pipelines[0].multisample.count = SampleCount::Four;
+        model.textures.push(Texture {
+            typ: TextureType::Srgb,
+            sample_count: SampleCount::Four,
+            ..texture()
+        });
+        model.passes[0].color_attachments[0].resolve = Some(TextureId(1));
+        let errors = model.validate();
+        let texture = TextureId(1);
+        let pass = PassId(0);
+        assert!(errors.contains(&ValidationError::ResolveTargetMultisampled { pass, texture }));
+        assert!(errors.contains(&ValidationError::ResolveTargetFormatMismatch { pass, texture }));
+
+        model.textures[1] = Texture {
+            sample_count: SampleCount::One,
+            ..model.textures[0].clone()
+        };
+        assert!(model.validate().is_empty());
+    }
+
+    #[test]
+    fn multisampled_not_d2() {
+        let mut model = model();
+        model.textures.push(Texture {
+            dimension: TextureDimension::D3,
+            sample_count: SampleCount::Four,
+            ..texture()
+        });
+        assert!(model
+            .validate()
+            .contains(&ValidationError::TextureMultisampledNotD2 {
+                texture: TextureId(1),
+            }));
+    }
+
+    #[test]
+    fn multisampled_mipmaps() {
+        let mut model = model();
+        model.textures[0].sample_count = SampleCount::Four;
+        model.textures[0].mip_levels = 2;
+        assert!(model
+            .validate()
+            .contains(&ValidationError::TextureMultisampledMipmaps {
+                texture: TextureId(0),
+            }));
+    }
+
+    #[test]
+    fn attachment_not_renderable() {
+        let mut model = model();
+        model.textures[0].typ = TextureType::Packed(PackedFormat::Rgb9e5Ufloat);
+        assert!(model
+            .validate()
+            .contains(&ValidationError::AttachmentNotRenderable {
+                pass: PassId(0),
+                texture: TextureId(0),
+            }));
+    }
+
+    #[test]
+    fn attachment_not_d2() {
+        let mut model = model();
+        model.textures[0].dimension = TextureDimension::D3;
+        assert!(model
+            .validate()
+            .contains(&ValidationError::AttachmentNotD2 {
+                pass: PassId(0),
+                texture: TextureId(0),
+            }));
+    }
+
+    #[test]
+    fn d1_texture_size() {
+        let mut model = model();
+        model.textures.push(Texture {
+            dimension: TextureDimension::D1,
+            ..texture()
+        });
+        assert!(model
+            .validate()
+            .contains(&ValidationError::TextureD1SizeInvalid {
+                texture: TextureId(1),
+            }));
+
+        model.textures[1].size.height = 1;
+        assert!(model.validate().is_empty());
+    }
+
+    #[test]
+    fn attachment_without_depth() {
+        let mut model = model();
+        model.textures.push(Texture {
+            typ: TextureType::Depth(DepthFormat::Depth32Float),
+            ..texture()
+        });
+        model.passes[0].depth_attachment = Some(DepthAttachment {
+            texture: TextureId(1),
+            ..Default::default()
+        });
+        assert!(model
+            .validate()
+            .contains(&ValidationError::PipelineAttachmentWithoutDepth {
+                pipeline: PipelineId(0),
+                pass: PassId(0),
+            }));
+    }
+
+    #[test]
+    fn color_formats_mismatch() {
+        let mut model = model();
+        model.textures.push(Texture {
+            typ: TextureType::Srgb,
+            ..texture()
+        });
+        let mut pass = model.passes[0].clone();
+        pass.color_attachments[0].texture = TextureId(1);
+        model.passes.push(pass);
+        assert!(model
+            .validate()
+            .contains(&ValidationError::PipelineColorFormatsMismatch {
+                pipeline: PipelineId(0),
+                pass: PassId(1),
+            }));
+
+        model.textures[1] = texture();
+        assert!(model.validate().is_empty());
+    }
+
+    #[test]
+    fn strip_index_format_mismatch() {
+        let mut model = model();
+        model.pipelines[0].primitive.topology = Topology::TriangleStrip;
+        model.pipelines[0].primitive.strip_index_format = Some(IndexFormat::Uint16);
+        assert!(model
+            .validate()
+            .contains(&ValidationError::PipelineStripIndexFormatMismatch {
+                pipeline: PipelineId(0),
+            }));
+
+        model.pipelines[0].primitive.strip_index_format = Some(IndexFormat::Uint32);
+        assert!(model.validate().is_empty());
+    }
+
+    #[test]
+    fn push_constant_stages_overlap() {
+        let mut model = model();
+        model.pipelines[0].push_constants = vec![
+            PushConstantRange {
+                stages: ShaderStages::Vertex,
+                range: 0..16,
+            },
+            PushConstantRange {
+                stages: ShaderStages::Both,
+                range: 16..32,
+            },
+        ];
+        assert!(model
+            .validate()
+            .contains(&ValidationError::PipelinePushConstantStagesOverlap {
+                pipeline: PipelineId(0),
+            }));
+
+        model.pipelines[0].push_constants[1].stages = ShaderStages::Fragment;
+        assert!(model.validate().is_empty());
+    }
+
+    #[test]
+    fn storage_texture_format() {
+        let mut model = model();
+        model.textures.push(Texture {
+            typ: TextureType::Srgb,
+            ..texture()
+        });
+        model.bind_groups[0].storage_textures.push(Binding {
+            index: 1,
+            shader_stages: ShaderStages::Fragment,
+            info: StorageTextureInfo {
+                texture: TextureId(1),
+                dimension: TextureViewDimension::D2,
+                access: StorageAccess::WriteOnly,
+                range: ViewRange::default(),
+            },
+        });
+        assert_eq!(
+            model.validate(),
+            vec![ValidationError::BindGroupStorageTextureUnsupported {
+                bind_group: BindGroupId(0),
+                texture: TextureId(1),
+            }]
+        );
+    }
+}
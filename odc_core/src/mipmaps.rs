@@ -0,0 +1,178 @@
+use crate::res;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+
+/// Fills mip chain of texture by successive downsampling of previous level.
+/// Views of sRGB textures decode on sampling and encode on store, so filtering is linear.
+pub struct MipmapGenerator {
+    sampler: wgpu::Sampler,
+    layout: wgpu::BindGroupLayout,
+    pipelines: HashMap<wgpu::TextureFormat, wgpu::RenderPipeline>,
+}
+
+impl MipmapGenerator {
+    pub fn new(device: &wgpu::Device, formats: impl Iterator<Item = wgpu::TextureFormat>) -> Self {
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("mipmap"),
+            min_filter: wgpu::FilterMode::Linear,
+            mag_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let layout = Self::create_bind_group_layout(device);
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("mipmap"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let shader_module =
+            device.create_shader_module(&wgpu::include_wgsl!("../data/shaders/mipmap.wgsl"));
+
+        let mut pipelines = HashMap::new();
+        for format in formats {
+            pipelines.entry(format).or_insert_with(|| {
+                Self::create_pipeline(device, &shader_module, &pipeline_layout, format)
+            });
+        }
+
+        Self {
+            sampler,
+            layout,
+            pipelines,
+        }
+    }
+
+    /// Returns `false` if there is no pipeline for texture format.
+    pub fn generate(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &res::Texture,
+    ) -> bool {
+        let pipeline = match self.pipelines.get(&texture.info.format) {
+            Some(pipeline) => pipeline,
+            None => return false,
+        };
+
+        for layer in 0..texture.info.size.depth_or_array_layers {
+            for level in 1..texture.info.mip_levels {
+                let source = Self::create_view(texture, layer, level - 1);
+                let target = Self::create_view(texture, layer, level);
+                let bind_group = self.create_bind_group(device, &source);
+
+                let attachment = wgpu::RenderPassColorAttachment {
+                    view: &target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: true,
+                    },
+                };
+                let descriptor = wgpu::RenderPassDescriptor {
+                    label: Some("mipmap"),
+                    color_attachments: &[attachment],
+                    depth_stencil_attachment: None,
+                };
+                let mut pass = encoder.begin_render_pass(&descriptor);
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.draw(0..3, 0..1);
+            }
+        }
+        true
+    }
+
+    fn create_view(texture: &res::Texture, layer: u32, level: u32) -> wgpu::TextureView {
+        texture.handle.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            base_mip_level: level,
+            mip_level_count: NonZeroU32::new(1),
+            base_array_layer: layer,
+            array_layer_count: NonZeroU32::new(1),
+            ..Default::default()
+        })
+    }
+
+    fn create_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        let texture_entry = wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+
+        let sampler_entry = wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        };
+
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("mipmap"),
+            entries: &[texture_entry, sampler_entry],
+        })
+    }
+
+    fn create_pipeline(
+        device: &wgpu::Device,
+        shader_module: &wgpu::ShaderModule,
+        layout: &wgpu::PipelineLayout,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::RenderPipeline {
+        let vertex = wgpu::VertexState {
+            module: shader_module,
+            entry_point: "vs_main",
+            buffers: &[],
+        };
+
+        let color_targets = [wgpu::ColorTargetState {
+            format,
+            blend: None,
+            write_mask: Default::default(),
+        }];
+
+        let fragment = Some(wgpu::FragmentState {
+            module: shader_module,
+            entry_point: "fs_main",
+            targets: &color_targets,
+        });
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("mipmap"),
+            layout: Some(layout),
+            vertex,
+            fragment,
+            primitive: Default::default(),
+            depth_stencil: None,
+            multisample: Default::default(),
+            multiview: None,
+        })
+    }
+
+    fn create_bind_group(
+        &self,
+        device: &wgpu::Device,
+        view: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        let texture_entry = wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(view),
+        };
+
+        let sampler_entry = wgpu::BindGroupEntry {
+            binding: 1,
+            resource: wgpu::BindingResource::Sampler(&self.sampler),
+        };
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("mipmap"),
+            layout: &self.layout,
+            entries: &[texture_entry, sampler_entry],
+        })
+    }
+}
@@ -2,6 +2,7 @@ use crate::BindGroups;
 
 pub struct Pipelines {
     pub render: Vec<RenderPipeline>,
+    pub compute: Vec<ComputePipeline>,
 }
 
 pub struct RenderPipeline {
@@ -10,42 +11,31 @@ pub struct RenderPipeline {
 }
 
 pub struct RenderPipelineInfo {
+    pub name: Option<String>,
     pub shader: RenderShaderInfo,
-    pub input: Option<RenderPipelineInput>,
+    pub input: RenderPipelineInput,
     pub bind_groups: Vec<usize>,
-    pub depth_test: bool,
+    pub push_constant_ranges: Vec<wgpu::PushConstantRange>,
+    pub depth_stencil: Option<wgpu::DepthStencilState>,
     pub color_targets: Vec<wgpu::ColorTargetState>,
-    pub sample_count: u32,
+    pub multisample: wgpu::MultisampleState,
+    pub primitive: wgpu::PrimitiveState,
 }
 
-impl RenderPipelineInfo {
-    pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
-
-    pub fn depth_test(&self) -> Option<wgpu::DepthStencilState> {
-        if !self.depth_test {
-            return None;
-        }
+pub struct ComputePipeline {
+    pub handle: wgpu::ComputePipeline,
+    pub info: ComputePipelineInfo,
+}
 
-        Some(wgpu::DepthStencilState {
-            format: Self::DEPTH_FORMAT,
-            depth_write_enabled: true,
-            depth_compare: wgpu::CompareFunction::Less,
-            stencil: wgpu::StencilState::default(),
-            bias: wgpu::DepthBiasState::default(),
-        })
-    }
+pub struct ComputePipelineInfo {
+    pub name: Option<String>,
+    pub shader: ComputeShaderInfo,
+    pub bind_groups: Vec<usize>,
+}
 
-    pub fn primitive_state() -> wgpu::PrimitiveState {
-        wgpu::PrimitiveState {
-            topology: wgpu::PrimitiveTopology::TriangleList,
-            strip_index_format: None,
-            front_face: wgpu::FrontFace::Ccw,
-            cull_mode: Some(wgpu::Face::Front),
-            unclipped_depth: false,
-            polygon_mode: wgpu::PolygonMode::Fill,
-            conservative: false,
-        }
-    }
+pub struct ComputeShaderInfo {
+    pub source: String,
+    pub cs_main: String,
 }
 
 pub struct RenderShaderInfo {
@@ -55,18 +45,20 @@ pub struct RenderShaderInfo {
 }
 
 pub struct RenderPipelineInput {
-    pub vertex: InputBufferLayout,
-    pub instance: InputBufferLayout,
+    pub index: Option<usize>,
+    pub index_format: wgpu::IndexFormat,
+    pub streams: Vec<InputBufferLayout>,
 }
 
 pub struct InputBufferLayout {
+    pub buffer_index: usize,
     pub stride: u64,
     pub step_mode: wgpu::VertexStepMode,
     pub attributes: Vec<wgpu::VertexAttribute>,
 }
 
 impl InputBufferLayout {
-    pub fn raw_input_layout(&self) -> wgpu::VertexBufferLayout {
+    pub fn raw_input_layout(&self) -> wgpu::VertexBufferLayout<'_> {
         wgpu::VertexBufferLayout {
             array_stride: self.stride,
             step_mode: self.step_mode,
@@ -89,28 +81,26 @@ impl<'a> PipelinesFactory<'a> {
     }
 
     pub fn create_render_pipeline(&self, info: RenderPipelineInfo) -> RenderPipeline {
-        let shader_module = self.create_shader_module(&info.shader);
+        let label = info.name.as_deref();
+        let shader_module = self.create_shader_module(label, &info.shader.source);
 
-        let input_layouts = info.input.as_ref().map(|input| {
-            [
-                input.vertex.raw_input_layout(),
-                input.instance.raw_input_layout(),
-            ]
-        });
-        let buffers = match input_layouts.as_ref() {
-            Some(arr) => arr.as_slice(),
-            None => [].as_slice(),
-        };
+        let buffers: Vec<_> = info
+            .input
+            .streams
+            .iter()
+            .map(|stream| stream.raw_input_layout())
+            .collect();
 
         let vertex = wgpu::VertexState {
             module: &shader_module,
             entry_point: &info.shader.vs_main,
-            buffers,
+            buffers: &buffers,
         };
 
-        let layout = self.create_pipeline_layout(&info.bind_groups);
-        let primitive = RenderPipelineInfo::primitive_state();
-        let depth_stencil = info.depth_test();
+        let layout =
+            self.create_pipeline_layout(label, &info.bind_groups, &info.push_constant_ranges);
+        let primitive = info.primitive;
+        let depth_stencil = info.depth_stencil.clone();
 
         let fragment = Some(wgpu::FragmentState {
             module: &shader_module,
@@ -118,13 +108,10 @@ impl<'a> PipelinesFactory<'a> {
             targets: &info.color_targets,
         });
 
-        let multisample = wgpu::MultisampleState {
-            count: info.sample_count,
-            ..Default::default()
-        };
+        let multisample = info.multisample;
 
         let descriptor = wgpu::RenderPipelineDescriptor {
-            label: None,
+            label,
             layout: Some(&layout),
             vertex,
             primitive,
@@ -138,28 +125,46 @@ impl<'a> PipelinesFactory<'a> {
         RenderPipeline { handle, info }
     }
 
-    fn create_shader_module(&self, shader: &RenderShaderInfo) -> wgpu::ShaderModule {
+    pub fn create_compute_pipeline(&self, info: ComputePipelineInfo) -> ComputePipeline {
+        let label = info.name.as_deref();
+        let shader_module = self.create_shader_module(label, &info.shader.source);
+        let layout = self.create_pipeline_layout(label, &info.bind_groups, &[]);
+
+        let descriptor = wgpu::ComputePipelineDescriptor {
+            label,
+            layout: Some(&layout),
+            module: &shader_module,
+            entry_point: &info.shader.cs_main,
+        };
+
+        let handle = self.device.create_compute_pipeline(&descriptor);
+        ComputePipeline { handle, info }
+    }
+
+    fn create_shader_module(&self, label: Option<&str>, source: &str) -> wgpu::ShaderModule {
         use std::borrow::Cow;
 
-        let shader_src = Cow::Borrowed(shader.source.as_str());
+        let shader_src = Cow::Borrowed(source);
         let source = wgpu::ShaderSource::Wgsl(shader_src);
-        let descriptor = wgpu::ShaderModuleDescriptor {
-            label: None,
-            source,
-        };
+        let descriptor = wgpu::ShaderModuleDescriptor { label, source };
         self.device.create_shader_module(&descriptor)
     }
 
-    fn create_pipeline_layout(&self, bind_group_indices: &[usize]) -> wgpu::PipelineLayout {
+    fn create_pipeline_layout(
+        &self,
+        label: Option<&str>,
+        bind_group_indices: &[usize],
+        push_constant_ranges: &[wgpu::PushConstantRange],
+    ) -> wgpu::PipelineLayout {
         let layouts: Vec<_> = bind_group_indices
             .iter()
             .map(|index| &self.bind_groups.0[*index].layout)
             .collect();
 
         let descriptor = wgpu::PipelineLayoutDescriptor {
-            label: None,
+            label,
             bind_group_layouts: &layouts,
-            push_constant_ranges: &[],
+            push_constant_ranges,
         };
 
         self.device.create_pipeline_layout(&descriptor)
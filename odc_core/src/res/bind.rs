@@ -1,4 +1,4 @@
-use crate::{BufferType, Resources};
+use crate::{mdl, OdcError, OdcResult, Resources};
 use std::num::NonZeroU64;
 use wgpu::TextureView;
 
@@ -17,17 +17,130 @@ pub struct BindGroup {
 }
 
 pub struct BindGroupInfo {
-    pub uniform: Option<Binding<UniformBindingInfo>>,
+    pub name: Option<String>,
+    pub uniforms: Vec<Binding<UniformBindingInfo>>,
     pub textures: Vec<Binding<TextureBindingInfo>>,
     pub samplers: Vec<Binding<SamplerBindingInfo>>,
+    pub storage_buffers: Vec<Binding<StorageBufferBindingInfo>>,
+    pub storage_textures: Vec<Binding<StorageTextureBindingInfo>>,
 }
 
 impl BindGroupInfo {
     pub fn bindings_count(&self) -> usize {
         self.textures.len()
             + self.samplers.len()
-            + self.uniform.as_ref().map(|_| 1).unwrap_or_default()
+            + self.storage_buffers.len()
+            + self.storage_textures.len()
+            + self.uniforms.len()
     }
+
+    pub fn dynamic_offsets_count(&self) -> usize {
+        let uniform = self.uniforms.iter().filter(|b| b.info.dynamic_offset);
+        let storage = self
+            .storage_buffers
+            .iter()
+            .filter(|b| b.info.dynamic_offset);
+        uniform.count() + storage.count()
+    }
+
+    /// Mirrors wgpu rules: offsets are given in order of binding indices, are aligned to limit of
+    /// their binding type and keep bindings within their buffers.
+    pub fn check_dynamic_offsets(
+        &self,
+        bind_group: mdl::BindGroupId,
+        offsets: &[u32],
+        limits: &wgpu::Limits,
+        buffer_size: impl Fn(usize) -> u64,
+    ) -> OdcResult<()> {
+        let uniforms = self
+            .uniforms
+            .iter()
+            .filter(|b| b.info.dynamic_offset)
+            .map(|b| DynamicBinding {
+                index: b.index,
+                buffer_index: b.info.buffer_index,
+                offset: b.info.offset,
+                size: Some(b.info.size),
+                alignment: limits.min_uniform_buffer_offset_alignment,
+            });
+        let storage = self
+            .storage_buffers
+            .iter()
+            .filter(|b| b.info.dynamic_offset)
+            .map(|b| DynamicBinding {
+                index: b.index,
+                buffer_index: b.info.buffer_index,
+                offset: b.info.offset,
+                size: b.info.size,
+                alignment: limits.min_storage_buffer_offset_alignment,
+            });
+        let mut bindings: Vec<_> = uniforms.chain(storage).collect();
+        if offsets.len() != bindings.len() {
+            return Err(OdcError::DynamicOffsetsMismatch {
+                bind_group,
+                expected: bindings.len(),
+                actual: offsets.len(),
+            });
+        }
+
+        bindings.sort_by_key(|b| b.index);
+        for (binding, &offset) in bindings.iter().zip(offsets) {
+            if offset % binding.alignment != 0 {
+                return Err(OdcError::UnalignedDynamicOffset {
+                    bind_group,
+                    binding: binding.index,
+                    offset,
+                    alignment: binding.alignment,
+                });
+            }
+
+            // Binding without size spans buffer to its end.
+            let buffer_size = buffer_size(binding.buffer_index);
+            let size = match binding.size {
+                Some(size) if size > 0 => size,
+                _ => buffer_size.saturating_sub(binding.offset),
+            };
+            let end = binding
+                .offset
+                .checked_add(offset as u64)
+                .and_then(|start| start.checked_add(size));
+            if !end.is_some_and(|end| end <= buffer_size) {
+                return Err(OdcError::DynamicOffsetOutOfBounds {
+                    bind_group,
+                    binding: binding.index,
+                    offset,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn layout_entries(&self) -> Vec<wgpu::BindGroupLayoutEntry> {
+        let mut entries = Vec::with_capacity(self.bindings_count());
+        entries.extend(self.uniforms.iter().map(|b| b.layout_entry()));
+        entries.extend(self.textures.iter().map(|b| b.layout_entry()));
+        entries.extend(self.samplers.iter().map(|b| b.layout_entry()));
+        entries.extend(self.storage_buffers.iter().map(|b| b.layout_entry()));
+        entries.extend(self.storage_textures.iter().map(|b| b.layout_entry()));
+        entries
+    }
+
+    /// Bind groups with identical layout entries can replace each other in pipeline.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        let mut entries = self.layout_entries();
+        let mut other_entries = other.layout_entries();
+        entries.sort_by_key(|e| e.binding);
+        other_entries.sort_by_key(|e| e.binding);
+        entries == other_entries
+    }
+}
+
+struct DynamicBinding {
+    index: u32,
+    buffer_index: usize,
+    offset: u64,
+    size: Option<u64>,
+    alignment: u32,
 }
 
 pub struct Binding<BindingInfo> {
@@ -41,7 +154,7 @@ impl Binding<UniformBindingInfo> {
         let size = NonZeroU64::new(self.info.size);
         let ty = wgpu::BindingType::Buffer {
             ty: wgpu::BufferBindingType::Uniform,
-            has_dynamic_offset: false,
+            has_dynamic_offset: self.info.dynamic_offset,
             min_binding_size: size,
         };
 
@@ -68,7 +181,10 @@ impl Binding<UniformBindingInfo> {
 
 impl Binding<TextureBindingInfo> {
     pub fn layout_entry(&self) -> wgpu::BindGroupLayoutEntry {
-        let sample_type = self.info.format.describe().sample_type;
+        let sample_type = match self.info.range.aspect {
+            wgpu::TextureAspect::StencilOnly => wgpu::TextureSampleType::Uint,
+            _ => self.info.format.describe().sample_type,
+        };
         let ty = wgpu::BindingType::Texture {
             sample_type,
             view_dimension: self.info.dimension,
@@ -112,15 +228,74 @@ impl Binding<SamplerBindingInfo> {
     }
 }
 
+impl Binding<StorageBufferBindingInfo> {
+    pub fn layout_entry(&self) -> wgpu::BindGroupLayoutEntry {
+        let size = self.info.size.and_then(NonZeroU64::new);
+        let ty = wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage {
+                read_only: self.info.read_only,
+            },
+            has_dynamic_offset: self.info.dynamic_offset,
+            min_binding_size: size,
+        };
+
+        wgpu::BindGroupLayoutEntry {
+            binding: self.index,
+            visibility: self.visibility,
+            ty,
+            count: None,
+        }
+    }
+
+    pub fn entry<'a>(&self, buffer: &'a wgpu::Buffer) -> wgpu::BindGroupEntry<'a> {
+        let size = self.info.size.and_then(NonZeroU64::new);
+        wgpu::BindGroupEntry {
+            binding: self.index,
+            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                buffer,
+                size,
+                offset: self.info.offset,
+            }),
+        }
+    }
+}
+
+impl Binding<StorageTextureBindingInfo> {
+    pub fn layout_entry(&self) -> wgpu::BindGroupLayoutEntry {
+        let ty = wgpu::BindingType::StorageTexture {
+            access: self.info.access,
+            format: self.info.format,
+            view_dimension: self.info.dimension,
+        };
+
+        wgpu::BindGroupLayoutEntry {
+            binding: self.index,
+            visibility: self.visibility,
+            ty,
+            count: None,
+        }
+    }
+
+    pub fn entry<'a>(&self, view: &'a wgpu::TextureView) -> wgpu::BindGroupEntry<'a> {
+        wgpu::BindGroupEntry {
+            binding: self.index,
+            resource: wgpu::BindingResource::TextureView(view),
+        }
+    }
+}
+
 pub struct UniformBindingInfo {
+    pub buffer_index: usize,
     pub size: u64,
     pub offset: u64,
+    pub dynamic_offset: bool,
 }
 
 pub struct TextureBindingInfo {
     pub texture_index: usize,
     pub format: wgpu::TextureFormat,
     pub dimension: wgpu::TextureViewDimension,
+    pub range: wgpu::ImageSubresourceRange,
 }
 
 pub struct SamplerBindingInfo {
@@ -128,6 +303,22 @@ pub struct SamplerBindingInfo {
     pub typ: wgpu::SamplerBindingType,
 }
 
+pub struct StorageBufferBindingInfo {
+    pub buffer_index: usize,
+    pub offset: u64,
+    pub size: Option<u64>,
+    pub read_only: bool,
+    pub dynamic_offset: bool,
+}
+
+pub struct StorageTextureBindingInfo {
+    pub texture_index: usize,
+    pub format: wgpu::TextureFormat,
+    pub dimension: wgpu::TextureViewDimension,
+    pub access: wgpu::StorageTextureAccess,
+    pub range: wgpu::ImageSubresourceRange,
+}
+
 pub struct BindGroupFactory<'a> {
     device: &'a wgpu::Device,
     resources: &'a Resources,
@@ -141,9 +332,10 @@ impl<'a> BindGroupFactory<'a> {
     pub fn refresh_bind_group(&self, bind_group: &mut BindGroup) {
         let info = &bind_group.info;
 
-        let views = self.texture_views(&info.textures);
-        let entries = self.collect_entries(info, views.iter());
-        let handle = self.create_raw_handle(&bind_group.layout, &entries);
+        let views = self.texture_views(info);
+        let storage_views = self.storage_texture_views(info);
+        let entries = self.collect_entries(info, views.iter(), storage_views.iter());
+        let handle = self.create_raw_handle(info, &bind_group.layout, &entries);
 
         bind_group.handle = handle;
     }
@@ -151,9 +343,10 @@ impl<'a> BindGroupFactory<'a> {
     pub fn create_bind_group(&self, info: BindGroupInfo) -> BindGroup {
         let layout = self.create_bind_group_layout(&info);
 
-        let views = self.texture_views(&info.textures);
-        let entries = self.collect_entries(&info, views.iter());
-        let handle = self.create_raw_handle(&layout, &entries);
+        let views = self.texture_views(&info);
+        let storage_views = self.storage_texture_views(&info);
+        let entries = self.collect_entries(&info, views.iter(), storage_views.iter());
+        let handle = self.create_raw_handle(&info, &layout, &entries);
 
         BindGroup {
             handle,
@@ -164,11 +357,12 @@ impl<'a> BindGroupFactory<'a> {
 
     fn create_raw_handle(
         &self,
+        info: &BindGroupInfo,
         layout: &wgpu::BindGroupLayout,
         entries: &[wgpu::BindGroupEntry],
     ) -> wgpu::BindGroup {
         let descriptor = wgpu::BindGroupDescriptor {
-            label: None,
+            label: info.name.as_deref(),
             layout,
             entries,
         };
@@ -180,14 +374,15 @@ impl<'a> BindGroupFactory<'a> {
         &self,
         info: &BindGroupInfo,
         views: ViewsIter,
+        storage_views: ViewsIter,
     ) -> Vec<wgpu::BindGroupEntry<'b>>
     where
         'a: 'b,
         ViewsIter: Iterator<Item = &'b TextureView>,
     {
         let mut entries = Vec::with_capacity(info.bindings_count());
-        entries.extend(info.uniform.iter().map(|b| {
-            let buffer = self.resources.buffers.get(BufferType::Uniform);
+        entries.extend(info.uniforms.iter().map(|b| {
+            let buffer = &self.resources.buffers[b.info.buffer_index];
             b.entry(&buffer.handle)
         }));
 
@@ -202,31 +397,150 @@ impl<'a> BindGroupFactory<'a> {
             let sampler = &self.resources.samplers[b.info.sampler_index];
             b.entry(&sampler.handle)
         }));
+
+        entries.extend(info.storage_buffers.iter().map(|b| {
+            let buffer = &self.resources.buffers[b.info.buffer_index];
+            b.entry(&buffer.handle)
+        }));
+
+        entries.extend(
+            info.storage_textures
+                .iter()
+                .zip(storage_views)
+                .map(|(b, view)| b.entry(view)),
+        );
         entries
     }
 
-    fn texture_views(&self, textures: &[Binding<TextureBindingInfo>]) -> Vec<wgpu::TextureView> {
-        textures
+    fn texture_views(&self, info: &BindGroupInfo) -> Vec<wgpu::TextureView> {
+        info.textures
             .iter()
-            .map(|b| {
-                let index = b.info.texture_index;
-                let texture = &self.resources.textures[index];
-                let dimension = Some(b.info.dimension);
-                texture.create_view(dimension)
-            })
+            .map(|b| self.texture_view(b.info.texture_index, b.info.dimension, &b.info.range))
             .collect()
     }
 
-    fn create_bind_group_layout(&self, info: &BindGroupInfo) -> wgpu::BindGroupLayout {
-        let mut entries = Vec::with_capacity(info.bindings_count());
-        entries.extend(info.uniform.iter().map(|b| b.layout_entry()));
-        entries.extend(info.textures.iter().map(|b| b.layout_entry()));
-        entries.extend(info.samplers.iter().map(|b| b.layout_entry()));
+    fn storage_texture_views(&self, info: &BindGroupInfo) -> Vec<wgpu::TextureView> {
+        info.storage_textures
+            .iter()
+            .map(|b| self.texture_view(b.info.texture_index, b.info.dimension, &b.info.range))
+            .collect()
+    }
 
+    fn texture_view(
+        &self,
+        index: usize,
+        dimension: wgpu::TextureViewDimension,
+        range: &wgpu::ImageSubresourceRange,
+    ) -> wgpu::TextureView {
+        let texture = &self.resources.textures[index];
+        texture.create_view(Some(dimension), range)
+    }
+
+    fn create_bind_group_layout(&self, info: &BindGroupInfo) -> wgpu::BindGroupLayout {
+        let entries = info.layout_entries();
         let descriptor = wgpu::BindGroupLayoutDescriptor {
-            label: None,
+            label: info.name.as_deref(),
             entries: &entries,
         };
         self.device.create_bind_group_layout(&descriptor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{BindGroupInfo, Binding, StorageBufferBindingInfo, UniformBindingInfo};
+    use crate::mdl::BindGroupId;
+    use crate::OdcError;
+
+    const BUFFER_SIZE: u64 = 1024;
+
+    /// Dynamic uniform at binding 1 and dynamic storage buffer at binding 0, both of 256 bytes.
+    fn bind_group() -> BindGroupInfo {
+        let uniform = Binding {
+            index: 1,
+            visibility: wgpu::ShaderStages::VERTEX,
+            info: UniformBindingInfo {
+                buffer_index: 0,
+                size: 256,
+                offset: 0,
+                dynamic_offset: true,
+            },
+        };
+        let storage = Binding {
+            index: 0,
+            visibility: wgpu::ShaderStages::VERTEX,
+            info: StorageBufferBindingInfo {
+                buffer_index: 1,
+                offset: 256,
+                size: Some(256),
+                read_only: true,
+                dynamic_offset: true,
+            },
+        };
+        BindGroupInfo {
+            name: None,
+            uniforms: vec![uniform],
+            textures: vec![],
+            samplers: vec![],
+            storage_buffers: vec![storage],
+            storage_textures: vec![],
+        }
+    }
+
+    fn check(offsets: &[u32]) -> Result<(), OdcError> {
+        let limits = wgpu::Limits::default();
+        bind_group().check_dynamic_offsets(BindGroupId(0), offsets, &limits, |_| BUFFER_SIZE)
+    }
+
+    #[test]
+    fn dynamic_offsets_valid() {
+        assert!(check(&[0, 0]).is_ok());
+        assert!(check(&[512, 768]).is_ok());
+    }
+
+    #[test]
+    fn dynamic_offsets_mismatch() {
+        let result = check(&[0]);
+        assert!(matches!(
+            result,
+            Err(OdcError::DynamicOffsetsMismatch {
+                expected: 2,
+                actual: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn dynamic_offset_unaligned() {
+        let result = check(&[0, 128]);
+        assert!(matches!(
+            result,
+            Err(OdcError::UnalignedDynamicOffset {
+                binding: 1,
+                offset: 128,
+                alignment: 256,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn dynamic_offset_out_of_bounds() {
+        let result = check(&[768, 0]);
+        assert!(matches!(
+            result,
+            Err(OdcError::DynamicOffsetOutOfBounds {
+                binding: 0,
+                offset: 768,
+                ..
+            })
+        ));
+
+        let result = check(&[0, u32::MAX - 255]);
+        assert!(matches!(
+            result,
+            Err(OdcError::DynamicOffsetOutOfBounds { binding: 1, .. })
+        ));
+    }
+}
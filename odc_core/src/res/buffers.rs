@@ -1,61 +1,87 @@
-pub struct Buffers {
-    index: Buffer,
-    vertex: Buffer,
-    instance: Buffer,
-    uniform: Buffer,
+use crate::{mdl, OdcError, OdcResult};
+
+pub struct Buffer {
+    pub handle: wgpu::Buffer,
+    pub info: BufferInfo,
+}
+
+#[derive(Debug, Clone)]
+pub struct BufferInfo {
+    pub name: Option<String>,
+    pub size: u64,
+    pub usage: wgpu::BufferUsages,
 }
 
-impl Buffers {
-    pub fn new(index: Buffer, vertex: Buffer, instance: Buffer, uniform: Buffer) -> Self {
-        Self {
-            index,
-            vertex,
-            instance,
-            uniform,
+impl BufferInfo {
+    /// Buffer of `id` must have `INDIRECT` usage and fit `count` arguments of `args_size` bytes
+    /// at `offset`.
+    pub fn check_indirect(
+        &self,
+        id: mdl::BufferId,
+        offset: u64,
+        count: u32,
+        args_size: u64,
+    ) -> OdcResult<()> {
+        if !self.usage.contains(wgpu::BufferUsages::INDIRECT) {
+            return Err(OdcError::BufferNotIndirect(id));
+        }
+        let end = args_size
+            .checked_mul(count as u64)
+            .and_then(|size| size.checked_add(offset));
+        if !end.is_some_and(|end| end <= self.size) {
+            return Err(OdcError::IndirectOutOfBounds {
+                buffer: id,
+                offset,
+                count,
+            });
         }
+        Ok(())
     }
+}
 
-    pub fn get(&self, typ: BufferType) -> &Buffer {
-        match typ {
-            BufferType::Index => &self.index,
-            BufferType::Vertex => &self.vertex,
-            BufferType::Instance => &self.instance,
-            BufferType::Uniform => &self.uniform,
+#[cfg(test)]
+mod tests {
+    use super::BufferInfo;
+    use crate::mdl::BufferId;
+    use crate::{DrawData, OdcError};
+
+    fn buffer(usage: wgpu::BufferUsages) -> BufferInfo {
+        BufferInfo {
+            name: None,
+            size: 64,
+            usage,
         }
     }
 
-    pub fn bind_buffers<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
-        pass.set_index_buffer(self.index.handle.slice(..), wgpu::IndexFormat::Uint32);
-        pass.set_vertex_buffer(0, self.vertex.handle.slice(..));
-        pass.set_vertex_buffer(1, self.instance.handle.slice(..));
+    #[test]
+    fn indirect_valid() {
+        let info = buffer(wgpu::BufferUsages::INDIRECT);
+        let size = DrawData::INDIRECT_ARGS_SIZE;
+        assert!(info.check_indirect(BufferId(0), 0, 4, size).is_ok());
+        assert!(info.check_indirect(BufferId(0), 48, 1, size).is_ok());
     }
 
-    pub fn replace(&mut self, typ: BufferType, new_buffer: Buffer) -> Buffer {
-        let buf = match typ {
-            BufferType::Index => &mut self.index,
-            BufferType::Vertex => &mut self.vertex,
-            BufferType::Instance => &mut self.instance,
-            BufferType::Uniform => &mut self.uniform,
-        };
-        std::mem::replace(buf, new_buffer)
+    #[test]
+    fn indirect_usage_missing() {
+        let info = buffer(wgpu::BufferUsages::VERTEX);
+        let result = info.check_indirect(BufferId(0), 0, 1, DrawData::INDIRECT_ARGS_SIZE);
+        assert!(matches!(
+            result,
+            Err(OdcError::BufferNotIndirect(BufferId(0)))
+        ));
     }
-}
 
-pub struct Buffer {
-    pub handle: wgpu::Buffer,
-    pub info: BufferInfo,
-}
+    #[test]
+    fn indirect_out_of_bounds() {
+        let info = buffer(wgpu::BufferUsages::INDIRECT);
+        let size = DrawData::INDEXED_INDIRECT_ARGS_SIZE;
+        let result = info.check_indirect(BufferId(0), 0, 4, size);
+        assert!(matches!(result, Err(OdcError::IndirectOutOfBounds { .. })));
 
-#[derive(Debug, Clone)]
-pub struct BufferInfo {
-    pub size: u64,
-    pub usage: wgpu::BufferUsages,
-}
+        let result = info.check_indirect(BufferId(0), 60, 1, size);
+        assert!(matches!(result, Err(OdcError::IndirectOutOfBounds { .. })));
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum BufferType {
-    Index,
-    Vertex,
-    Instance,
-    Uniform,
+        let result = info.check_indirect(BufferId(0), u64::MAX, 1, size);
+        assert!(matches!(result, Err(OdcError::IndirectOutOfBounds { .. })));
+    }
 }
@@ -3,17 +3,18 @@ mod buffers;
 mod samplers;
 mod textures;
 
+use crate::{mdl, OdcError, OdcResult};
 pub use bind::{
-    BindGroupFactory, BindGroupInfo, BindGroups, Binding, SamplerBindingInfo, TextureBindingInfo,
-    UniformBindingInfo,
+    BindGroupFactory, BindGroupInfo, BindGroups, Binding, SamplerBindingInfo,
+    StorageBufferBindingInfo, StorageTextureBindingInfo, TextureBindingInfo, UniformBindingInfo,
 };
-pub use buffers::{Buffer, BufferInfo, BufferType, Buffers};
+pub use buffers::{Buffer, BufferInfo};
 pub use samplers::{Sampler, SamplerInfo};
 use std::collections::HashMap;
 pub use textures::{Texture, TextureInfo};
 
 pub struct Resources {
-    pub buffers: Buffers,
+    pub buffers: Vec<Buffer>,
     pub textures: Vec<Texture>,
     pub samplers: Vec<Sampler>,
     pub stock: Stock,
@@ -23,49 +24,60 @@ impl Resources {
     pub fn insert_stock_buffer(
         &mut self,
         device: &wgpu::Device,
-        typ: BufferType,
+        id: mdl::BufferId,
         name: String,
         size: Option<u64>,
-    ) {
-        let mut info = self.buffers.get(typ).info.clone();
+    ) -> OdcResult<()> {
+        let buffer = self.buffers.get(id.0).ok_or(OdcError::BufferNotFound(id))?;
+        let mut info = buffer.info.clone();
         info.size = size.unwrap_or(info.size);
         let factory = ResourceFactory::new(device);
         let new_buffer = factory.create_buffer(info);
-        self.stock.insert_buffer(name, typ, new_buffer)
+        self.stock.insert_buffer(name, id, new_buffer);
+        Ok(())
     }
 
-    pub fn remove_stock_buffer(&mut self, name: &str) {
-        self.stock.remove_buffer(name);
+    pub fn remove_stock_buffer(&mut self, name: &str) -> OdcResult<()> {
+        self.stock.remove_buffer(name)?;
+        Ok(())
     }
 
-    pub fn swap_stock_buffer(&mut self, name: &str) {
-        let (name, (typ, buffer)) = self.stock.remove_buffer(name);
-        let old_buffer = self.buffers.replace(typ, buffer);
-        self.stock.insert_buffer(name, typ, old_buffer);
+    pub fn swap_stock_buffer(&mut self, name: &str) -> OdcResult<()> {
+        let (name, (id, buffer)) = self.stock.remove_buffer(name)?;
+        let old_buffer = std::mem::replace(&mut self.buffers[id.0], buffer);
+        self.stock.insert_buffer(name, id, old_buffer);
+        Ok(())
     }
 
     pub fn insert_stock_texture(
         &mut self,
         device: &wgpu::Device,
-        id: usize,
+        id: mdl::TextureId,
         name: String,
         size: Option<wgpu::Extent3d>,
-    ) {
-        let mut info = self.textures[id].info;
+    ) -> OdcResult<()> {
+        let texture = self
+            .textures
+            .get(id.0)
+            .ok_or(OdcError::TextureNotFound(id))?;
+        let mut info = texture.info.clone();
         info.size = size.unwrap_or(info.size);
         let factory = ResourceFactory::new(device);
         let new_texture = factory.create_texture(info);
         self.stock.insert_texture(name, id, new_texture);
+        Ok(())
     }
 
-    pub fn swap_stock_texture(&mut self, name: &str) {
-        let (name, (id, texture)) = self.stock.remove_texture(name);
-        let replaced = std::mem::replace(&mut self.textures[id], texture);
+    pub fn swap_stock_texture(&mut self, name: &str) -> OdcResult<()> {
+        let (name, (id, texture)) = self.stock.remove_texture(name)?;
+        let replaced = std::mem::replace(&mut self.textures[id.0], texture);
         self.stock.insert_texture(name, id, replaced);
+        Ok(())
     }
 
-    pub fn remove_stock_texture(&mut self, name: &str) {
-        self.stock.remove_texture(name);
+    pub fn remove_stock_texture(&mut self, name: &str) -> OdcResult<()> {
+        self.stock.remove_texture(name)?;
+        Ok(())
     }
 }
 
@@ -80,7 +92,7 @@ impl<'a> ResourceFactory<'a> {
 
     pub fn create_buffer(&self, info: BufferInfo) -> Buffer {
         let handle = self.device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
+            label: info.name.as_deref(),
             size: info.size,
             usage: info.usage,
             mapped_at_creation: false,
@@ -91,11 +103,11 @@ impl<'a> ResourceFactory<'a> {
 
     pub fn create_texture(&self, info: TextureInfo) -> Texture {
         let handle = self.device.create_texture(&wgpu::TextureDescriptor {
-            label: None,
+            label: info.name.as_deref(),
             size: info.size,
             format: info.format,
             usage: info.usages,
-            dimension: wgpu::TextureDimension::D2,
+            dimension: info.dimension,
             mip_level_count: info.mip_levels,
             sample_count: info.sample_count,
         });
@@ -105,15 +117,18 @@ impl<'a> ResourceFactory<'a> {
 
     pub fn create_sampler(&self, info: SamplerInfo) -> Sampler {
         let handle = self.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: info.name.as_deref(),
             address_mode_u: info.u_address,
             address_mode_v: info.v_address,
             address_mode_w: info.w_address,
-            min_filter: info.mode,
-            mag_filter: info.mode,
-            mipmap_filter: info.mode,
+            min_filter: info.min_filter,
+            mag_filter: info.mag_filter,
+            mipmap_filter: info.mipmap_filter,
+            lod_min_clamp: info.lod_min_clamp,
+            lod_max_clamp: info.lod_max_clamp,
             compare: info.compare,
             anisotropy_clamp: info.anisotropy,
-            ..Default::default()
+            border_color: info.border_color,
         });
 
         Sampler { handle, info }
@@ -122,32 +137,40 @@ impl<'a> ResourceFactory<'a> {
 
 #[derive(Default)]
 pub struct Stock {
-    buffers: HashMap<String, (BufferType, Buffer)>,
-    textures: HashMap<String, (usize, Texture)>,
+    buffers: HashMap<String, (mdl::BufferId, Buffer)>,
+    textures: HashMap<String, (mdl::TextureId, Texture)>,
 }
 
 impl Stock {
-    pub fn buffer(&self, name: &str) -> &(BufferType, Buffer) {
-        &self.buffers[name]
+    pub fn buffer(&self, name: &str) -> OdcResult<&(mdl::BufferId, Buffer)> {
+        self.buffers
+            .get(name)
+            .ok_or_else(|| OdcError::StockBufferNotFound(name.into()))
     }
 
-    pub fn insert_buffer(&mut self, name: String, typ: BufferType, buffer: Buffer) {
-        self.buffers.insert(name, (typ, buffer));
+    pub fn insert_buffer(&mut self, name: String, id: mdl::BufferId, buffer: Buffer) {
+        self.buffers.insert(name, (id, buffer));
     }
 
-    pub fn remove_buffer(&mut self, name: &str) -> (String, (BufferType, Buffer)) {
-        self.buffers.remove_entry(name).unwrap()
+    pub fn remove_buffer(&mut self, name: &str) -> OdcResult<(String, (mdl::BufferId, Buffer))> {
+        self.buffers
+            .remove_entry(name)
+            .ok_or_else(|| OdcError::StockBufferNotFound(name.into()))
     }
 
-    pub fn texture(&self, name: &str) -> &(usize, Texture) {
-        &self.textures[name]
+    pub fn texture(&self, name: &str) -> OdcResult<&(mdl::TextureId, Texture)> {
+        self.textures
+            .get(name)
+            .ok_or_else(|| OdcError::StockTextureNotFound(name.into()))
     }
 
-    pub fn insert_texture(&mut self, name: String, id: usize, texture: Texture) {
+    pub fn insert_texture(&mut self, name: String, id: mdl::TextureId, texture: Texture) {
         self.textures.insert(name, (id, texture));
     }
 
-    pub fn remove_texture(&mut self, name: &str) -> (String, (usize, Texture)) {
-        self.textures.remove_entry(name).unwrap()
+    pub fn remove_texture(&mut self, name: &str) -> OdcResult<(String, (mdl::TextureId, Texture))> {
+        self.textures
+            .remove_entry(name)
+            .ok_or_else(|| OdcError::StockTextureNotFound(name.into()))
     }
 }
@@ -2,14 +2,21 @@ use std::num::NonZeroU8;
 
 pub struct Sampler {
     pub handle: wgpu::Sampler,
+    #[allow(dead_code)]
     pub info: SamplerInfo,
 }
 
 pub struct SamplerInfo {
-    pub mode: wgpu::FilterMode,
+    pub name: Option<String>,
+    pub min_filter: wgpu::FilterMode,
+    pub mag_filter: wgpu::FilterMode,
+    pub mipmap_filter: wgpu::FilterMode,
+    pub lod_min_clamp: f32,
+    pub lod_max_clamp: f32,
     pub anisotropy: Option<NonZeroU8>,
     pub compare: Option<wgpu::CompareFunction>,
     pub u_address: wgpu::AddressMode,
     pub v_address: wgpu::AddressMode,
     pub w_address: wgpu::AddressMode,
+    pub border_color: Option<wgpu::SamplerBorderColor>,
 }
@@ -4,19 +4,36 @@ pub struct Texture {
 }
 
 impl Texture {
-    pub fn create_view(&self, dimension: Option<wgpu::TextureViewDimension>) -> wgpu::TextureView {
+    pub fn create_view(
+        &self,
+        dimension: Option<wgpu::TextureViewDimension>,
+        range: &wgpu::ImageSubresourceRange,
+    ) -> wgpu::TextureView {
         self.handle.create_view(&wgpu::TextureViewDescriptor {
             dimension,
+            aspect: range.aspect,
+            base_mip_level: range.base_mip_level,
+            mip_level_count: range.mip_level_count,
+            base_array_layer: range.base_array_layer,
+            array_layer_count: range.array_layer_count,
             ..Default::default()
         })
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TextureInfo {
+    pub name: Option<String>,
     pub format: wgpu::TextureFormat,
+    pub dimension: wgpu::TextureDimension,
     pub size: wgpu::Extent3d,
     pub usages: wgpu::TextureUsages,
     pub mip_levels: u32,
     pub sample_count: u32,
 }
+
+impl TextureInfo {
+    pub fn has_stencil(&self) -> bool {
+        matches!(self.format, wgpu::TextureFormat::Depth24PlusStencil8)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::mdl;
+use crate::{mdl, OdcError, OdcResult};
 use wgpu::{PresentMode, Surface, SurfaceConfiguration, TextureFormat, TextureUsages};
 
 pub struct Swapchain {
@@ -7,12 +7,12 @@ pub struct Swapchain {
 }
 
 impl Swapchain {
-    pub fn new(surface: Surface, adapter: &wgpu::Adapter) -> Self {
+    pub fn new(surface: Surface, adapter: &wgpu::Adapter) -> OdcResult<Self> {
         let format = surface
             .get_preferred_format(adapter)
-            .expect("can't find suit surface format");
+            .ok_or(OdcError::SurfaceFormatNotFound)?;
 
-        Self { surface, format }
+        Ok(Self { surface, format })
     }
 
     pub fn resize(&self, device: &wgpu::Device, size: mdl::Size2d) {